@@ -0,0 +1,581 @@
+#![allow(dead_code)]
+pub mod fields {
+    use std::collections::BinaryHeap;
+    use std::fmt::{Debug, Display};
+
+    // Coefficient field used by the persistence algorithms. Like JavaPlex's field objects the
+    // arithmetic lives on the field itself, so fields with runtime parameters (Z/p) work.
+    pub trait Field: Clone + Debug {
+        type Element: Clone + Debug + PartialEq;
+
+        // Representation of the column being reduced by the persistence algorithms
+        type Column: WorkingColumn<Self>;
+
+        fn zero(&self) -> Self::Element;
+
+        fn one(&self) -> Self::Element;
+
+        fn add(&self, a: &Self::Element, b: &Self::Element) -> Self::Element;
+
+        fn negate(&self, a: &Self::Element) -> Self::Element;
+
+        fn multiply(&self, a: &Self::Element, b: &Self::Element) -> Self::Element;
+
+        // Panics when asked to invert zero
+        fn invert(&self, a: &Self::Element) -> Self::Element;
+
+        fn characteristic(&self) -> u32;
+
+        // Image of an integer under the canonical map Z -> F
+        fn value_of(&self, n: i64) -> Self::Element;
+
+        fn subtract(&self, a: &Self::Element, b: &Self::Element) -> Self::Element {
+            return self.add(a, &self.negate(b));
+        }
+
+        fn divide(&self, a: &Self::Element, b: &Self::Element) -> Self::Element {
+            return self.multiply(a, &self.invert(b));
+        }
+
+        fn is_zero(&self, a: &Self::Element) -> bool {
+            return *a == self.zero();
+        }
+    }
+
+    // Column with entries in the rows 0, ..., length - 1 that is reduced by adding multiples
+    // of sparse columns, which are sorted by row index without zero entries. The pivot is
+    // the non-zero entry with the largest row index.
+    pub trait WorkingColumn<F: Field> {
+        fn new(length: usize) -> Self;
+
+        // Replaces the entries of the column by the given sparse column
+        fn load(&mut self, entries: Vec<(usize, F::Element)>);
+
+        fn get_pivot(&mut self, field: &F) -> Option<(usize, F::Element)>;
+
+        fn subtract_multiple(
+            &mut self,
+            field: &F,
+            column: &[(usize, F::Element)],
+            factor: &F::Element,
+        );
+
+        // Takes the entries out as a sparse column, leaving the column empty
+        fn unload(&mut self, field: &F) -> Vec<(usize, F::Element)>;
+    }
+
+    // Working column over any field, kept as a sorted list of non-zero entries
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct SparseColumn<E> {
+        entries: Vec<(usize, E)>,
+    }
+
+    impl<F> WorkingColumn<F> for SparseColumn<F::Element>
+    where
+        F: Field,
+    {
+        fn new(_length: usize) -> Self {
+            Self {
+                entries: Vec::new(),
+            }
+        }
+
+        fn load(&mut self, entries: Vec<(usize, F::Element)>) {
+            self.entries = entries;
+        }
+
+        fn get_pivot(&mut self, _field: &F) -> Option<(usize, F::Element)> {
+            return self.entries.last().cloned();
+        }
+
+        // Merges the two sorted columns into a - factor * b
+        fn subtract_multiple(
+            &mut self,
+            field: &F,
+            column: &[(usize, F::Element)],
+            factor: &F::Element,
+        ) {
+            let a: Vec<(usize, F::Element)> = std::mem::take(&mut self.entries);
+            let b: &[(usize, F::Element)] = column;
+            let (mut i, mut j) = (0, 0);
+            self.entries.reserve(a.len() + b.len());
+
+            while i < a.len() || j < b.len() {
+                if j == b.len() || (i < a.len() && a[i].0 < b[j].0) {
+                    self.entries.push(a[i].clone());
+                    i += 1;
+                } else if i == a.len() || b[j].0 < a[i].0 {
+                    let coefficient: F::Element = field.negate(&field.multiply(factor, &b[j].1));
+                    self.entries.push((b[j].0, coefficient));
+                    j += 1;
+                } else {
+                    let coefficient: F::Element =
+                        field.subtract(&a[i].1, &field.multiply(factor, &b[j].1));
+
+                    if !field.is_zero(&coefficient) {
+                        self.entries.push((a[i].0, coefficient));
+                    }
+
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+
+        fn unload(&mut self, _field: &F) -> Vec<(usize, F::Element)> {
+            return std::mem::take(&mut self.entries);
+        }
+    }
+
+    // Z/2 with every element stored as a single bit
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub struct BooleanField;
+
+    impl Field for BooleanField {
+        type Element = bool;
+        type Column = BooleanColumn;
+
+        fn zero(&self) -> bool {
+            false
+        }
+
+        fn one(&self) -> bool {
+            true
+        }
+
+        fn add(&self, a: &bool, b: &bool) -> bool {
+            a ^ b
+        }
+
+        fn negate(&self, a: &bool) -> bool {
+            *a
+        }
+
+        fn multiply(&self, a: &bool, b: &bool) -> bool {
+            a & b
+        }
+
+        fn invert(&self, a: &bool) -> bool {
+            assert!(*a, "Cannot invert zero");
+            true
+        }
+
+        fn characteristic(&self) -> u32 {
+            2
+        }
+
+        fn value_of(&self, n: i64) -> bool {
+            n.rem_euclid(2) == 1
+        }
+    }
+
+    // Dense vector over Z/2 packed 64 coordinates to a word, so that vector addition is
+    // a word-wise xor
+    #[derive(Debug, Clone, PartialEq, Eq, Default)]
+    pub struct BooleanVector {
+        words: Vec<u64>,
+        length: usize,
+    }
+
+    impl BooleanVector {
+        pub fn new(length: usize) -> Self {
+            Self {
+                words: vec![0; length.div_ceil(64)],
+                length,
+            }
+        }
+
+        pub fn len(&self) -> usize {
+            return self.length;
+        }
+
+        pub fn is_empty(&self) -> bool {
+            return self.length == 0;
+        }
+
+        pub fn get(&self, index: usize) -> bool {
+            assert!(index < self.length, "Index {} out of bounds", index);
+
+            return (self.words[index / 64] >> (index % 64)) & 1 == 1;
+        }
+
+        pub fn set(&mut self, index: usize, value: bool) {
+            assert!(index < self.length, "Index {} out of bounds", index);
+
+            if value {
+                self.words[index / 64] |= 1 << (index % 64);
+            } else {
+                self.words[index / 64] &= !(1 << (index % 64));
+            }
+        }
+
+        pub fn flip(&mut self, index: usize) {
+            assert!(index < self.length, "Index {} out of bounds", index);
+
+            self.words[index / 64] ^= 1 << (index % 64);
+        }
+
+        pub fn add_assign(&mut self, other: &BooleanVector) {
+            assert_eq!(self.length, other.length, "Vector lengths differ");
+
+            self.words
+                .iter_mut()
+                .zip(other.words.iter())
+                .for_each(|(a, b)| *a ^= b);
+        }
+
+        pub fn is_zero(&self) -> bool {
+            return self.words.iter().all(|w| *w == 0);
+        }
+
+        // Indices of the non-zero coordinates, in increasing order
+        pub fn support(&self) -> Vec<usize> {
+            return (0..self.length).filter(|i| self.get(*i)).collect();
+        }
+    }
+
+    // Working column over Z/2, the analogue of PHAT's full pivot column. The entries live in
+    // a bit-packed vector, so adding a column flips bits, and a max-heap of the touched rows
+    // finds the pivot without scanning the whole vector.
+    #[derive(Debug, Clone, Default)]
+    pub struct BooleanColumn {
+        bits: BooleanVector,
+        touched_rows: BinaryHeap<usize>,
+    }
+
+    impl BooleanColumn {
+        fn flip(&mut self, row: usize) {
+            self.bits.flip(row);
+            self.touched_rows.push(row);
+        }
+    }
+
+    impl WorkingColumn<BooleanField> for BooleanColumn {
+        fn new(length: usize) -> Self {
+            Self {
+                bits: BooleanVector::new(length),
+                touched_rows: BinaryHeap::new(),
+            }
+        }
+
+        fn load(&mut self, entries: Vec<(usize, bool)>) {
+            self.unload(&BooleanField);
+            entries.into_iter().for_each(|(row, _)| self.flip(row));
+        }
+
+        fn get_pivot(&mut self, _field: &BooleanField) -> Option<(usize, bool)> {
+            // Rows whose bit was cleared again are stale and dropped on the way
+            while let Some(row) = self.touched_rows.peek() {
+                if self.bits.get(*row) {
+                    return Some((*row, true));
+                }
+
+                self.touched_rows.pop();
+            }
+
+            return None;
+        }
+
+        fn subtract_multiple(
+            &mut self,
+            _field: &BooleanField,
+            column: &[(usize, bool)],
+            factor: &bool,
+        ) {
+            if *factor {
+                column.iter().for_each(|(row, _)| self.flip(*row));
+            }
+        }
+
+        fn unload(&mut self, _field: &BooleanField) -> Vec<(usize, bool)> {
+            let mut entries: Vec<(usize, bool)> = Vec::new();
+
+            // Clearing the bits of a row also skips its duplicates in the heap
+            while let Some(row) = self.touched_rows.pop() {
+                if self.bits.get(row) {
+                    self.bits.set(row, false);
+                    entries.push((row, true));
+                }
+            }
+            entries.reverse();
+
+            return entries;
+        }
+    }
+
+    // Inverses are tabulated for primes below this bound, larger primes compute them on demand
+    const INVERSE_TABLE_BOUND: u32 = 1 << 16;
+
+    // Z/p for a prime p. As in JavaPlex's ModularIntField inverses are precomputed for small
+    // primes, for the others they come from the extended Euclidean algorithm.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct ModularIntField {
+        prime: u32,
+        inverses: Vec<u32>,
+    }
+
+    impl ModularIntField {
+        pub fn new(prime: u32) -> Self {
+            assert!(
+                prime >= 2
+                    && (2..prime as u64)
+                        .take_while(|d| d * d <= prime as u64)
+                        .all(|d| !(prime as u64).is_multiple_of(d)),
+                "{} is not prime",
+                prime
+            );
+
+            let mut inverses: Vec<u32> = Vec::new();
+
+            if prime < INVERSE_TABLE_BOUND {
+                inverses = vec![0; prime as usize];
+                for a in 1..prime {
+                    inverses[a as usize] = Self::compute_inverse(a, prime);
+                }
+            }
+
+            Self { prime, inverses }
+        }
+
+        // Inverse of a non-zero a modulo the prime, from the Bezout identity s * a + t * p = 1
+        fn compute_inverse(a: u32, prime: u32) -> u32 {
+            let (mut r, mut next_r): (i64, i64) = (prime as i64, a as i64);
+            let (mut s, mut next_s): (i64, i64) = (0, 1);
+
+            while next_r != 0 {
+                let quotient: i64 = r / next_r;
+                (r, next_r) = (next_r, r - quotient * next_r);
+                (s, next_s) = (next_s, s - quotient * next_s);
+            }
+
+            return s.rem_euclid(prime as i64) as u32;
+        }
+    }
+
+    impl Field for ModularIntField {
+        type Element = u32;
+        type Column = SparseColumn<u32>;
+
+        fn zero(&self) -> u32 {
+            0
+        }
+
+        fn one(&self) -> u32 {
+            1
+        }
+
+        fn add(&self, a: &u32, b: &u32) -> u32 {
+            ((*a as u64 + *b as u64) % self.prime as u64) as u32
+        }
+
+        fn negate(&self, a: &u32) -> u32 {
+            (self.prime - a % self.prime) % self.prime
+        }
+
+        fn multiply(&self, a: &u32, b: &u32) -> u32 {
+            ((*a as u64 * *b as u64) % self.prime as u64) as u32
+        }
+
+        fn invert(&self, a: &u32) -> u32 {
+            let a: u32 = a % self.prime;
+            assert!(a != 0, "Cannot invert zero");
+
+            if self.inverses.is_empty() {
+                Self::compute_inverse(a, self.prime)
+            } else {
+                self.inverses[a as usize]
+            }
+        }
+
+        fn characteristic(&self) -> u32 {
+            self.prime
+        }
+
+        fn value_of(&self, n: i64) -> u32 {
+            n.rem_euclid(self.prime as i64) as u32
+        }
+    }
+
+    // Exact rational number, always stored in lowest terms with a positive denominator
+    #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct Rational {
+        numerator: i64,
+        denominator: i64,
+    }
+
+    impl Rational {
+        pub fn new(numerator: i64, denominator: i64) -> Self {
+            assert!(denominator != 0, "The denominator must be non-zero");
+
+            return Self::reduce(numerator as i128, denominator as i128);
+        }
+
+        pub fn get_numerator(&self) -> i64 {
+            return self.numerator;
+        }
+
+        pub fn get_denominator(&self) -> i64 {
+            return self.denominator;
+        }
+
+        fn reduce(numerator: i128, denominator: i128) -> Self {
+            let mut a: i128 = numerator.abs();
+            let mut b: i128 = denominator.abs();
+
+            while b != 0 {
+                (a, b) = (b, a % b);
+            }
+
+            let divisor: i128 = if a == 0 { 1 } else { a } * denominator.signum();
+
+            Self {
+                numerator: i64::try_from(numerator / divisor).expect("Rational overflow"),
+                denominator: i64::try_from(denominator / divisor).expect("Rational overflow"),
+            }
+        }
+    }
+
+    impl Display for Rational {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            if self.denominator == 1 {
+                return write!(f, "{}", self.numerator);
+            }
+
+            return write!(f, "{}/{}", self.numerator, self.denominator);
+        }
+    }
+
+    impl Debug for Rational {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            return write!(f, "{}", self);
+        }
+    }
+
+    // The field of rational numbers Q, analogue of JavaPlex's RationalField
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub struct RationalField;
+
+    impl Field for RationalField {
+        type Element = Rational;
+        type Column = SparseColumn<Rational>;
+
+        fn zero(&self) -> Rational {
+            Rational::new(0, 1)
+        }
+
+        fn one(&self) -> Rational {
+            Rational::new(1, 1)
+        }
+
+        fn add(&self, a: &Rational, b: &Rational) -> Rational {
+            Rational::reduce(
+                a.numerator as i128 * b.denominator as i128
+                    + b.numerator as i128 * a.denominator as i128,
+                a.denominator as i128 * b.denominator as i128,
+            )
+        }
+
+        fn negate(&self, a: &Rational) -> Rational {
+            Rational::new(-a.numerator, a.denominator)
+        }
+
+        fn multiply(&self, a: &Rational, b: &Rational) -> Rational {
+            Rational::reduce(
+                a.numerator as i128 * b.numerator as i128,
+                a.denominator as i128 * b.denominator as i128,
+            )
+        }
+
+        fn invert(&self, a: &Rational) -> Rational {
+            assert!(a.numerator != 0, "Cannot invert zero");
+
+            Rational::new(a.denominator, a.numerator)
+        }
+
+        fn characteristic(&self) -> u32 {
+            0
+        }
+
+        fn value_of(&self, n: i64) -> Rational {
+            Rational::new(n, 1)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fields::{
+        BooleanField, BooleanVector, Field, ModularIntField, Rational, RationalField,
+    };
+
+    #[test]
+    fn test_boolean_field() {
+        let field: BooleanField = BooleanField;
+
+        assert!(!field.add(&true, &true));
+        assert!(field.subtract(&false, &true));
+        assert!(field.value_of(-3));
+        assert_eq!(field.characteristic(), 2);
+
+        let mut a: BooleanVector = BooleanVector::new(130);
+        let mut b: BooleanVector = BooleanVector::new(130);
+        a.set(3, true);
+        a.set(129, true);
+        b.set(129, true);
+        b.set(64, true);
+        a.add_assign(&b);
+
+        assert_eq!(a.support(), vec![3, 64]);
+        assert!(!a.is_zero());
+    }
+
+    #[test]
+    fn test_modular_int_field() {
+        let field: ModularIntField = ModularIntField::new(7);
+
+        for a in 1..7 {
+            assert_eq!(field.multiply(&a, &field.invert(&a)), 1);
+            assert!(field.is_zero(&field.add(&a, &field.negate(&a))));
+        }
+
+        assert_eq!(field.value_of(-1), 6);
+        assert_eq!(field.divide(&3, &5), 2);
+        assert_eq!(field.characteristic(), 7);
+    }
+
+    #[test]
+    fn test_large_prime_field() {
+        // The largest prime below 2^32, whose square does not fit in a u32
+        let field: ModularIntField = ModularIntField::new(4294967291);
+        let mersenne: ModularIntField = ModularIntField::new(2147483647);
+
+        for a in [1, 2, 12345, 4294967290] {
+            assert_eq!(field.multiply(&a, &field.invert(&a)), 1);
+        }
+
+        assert_eq!(mersenne.multiply(&3, &mersenne.invert(&3)), 1);
+        assert_eq!(field.value_of(-1), 4294967290);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_modular_int_field_rejects_composites() {
+        ModularIntField::new(9);
+    }
+
+    #[test]
+    fn test_rational_field() {
+        let field: RationalField = RationalField;
+        let half: Rational = Rational::new(2, -4);
+
+        assert_eq!(half, Rational::new(-1, 2));
+        assert_eq!(field.add(&half, &Rational::new(1, 3)), Rational::new(-1, 6));
+        assert_eq!(field.invert(&half), field.value_of(-2));
+        assert_eq!(
+            field.multiply(&half, &half).to_string(),
+            String::from("1/4")
+        );
+        assert!(field.is_zero(&field.subtract(&half, &half)));
+        assert_eq!(field.characteristic(), 0);
+    }
+}
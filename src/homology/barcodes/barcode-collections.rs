@@ -1,11 +1,22 @@
 #![allow(dead_code)]
 pub mod barcode_collections {
+    use std::cmp::Ordering;
     use std::fmt::Debug;
+    use std::ops::Sub;
 
-    use crate::intervals::intervals::Interval;
+    use crate::intervals::intervals::{Interval, IntervalOrdering};
     use crate::persistence_invariant_descriptor::persistence_invariant_descriptor::PersistenceInvariantDescriptor;
 
+    #[cfg(feature = "serde")]
+    use serde::{Deserialize, Serialize};
+
+    // Deserializing the descriptor rebuilds its intervals and generators by cloning the pairs
     #[derive(Clone)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(bound(deserialize = "T: Clone + Deserialize<'de>, G: Clone + Deserialize<'de>"))
+    )]
     pub struct AnnotatedBarcodeCollection<T, G> {
         use_left_closed_default: bool,
         use_right_closed_default: bool,
@@ -148,6 +159,87 @@ pub mod barcode_collections {
                 generating_cycle.unwrap_or_default(),
             );
         }
+
+        // Adds the interval exactly as given, keeping its own endpoint flags and infinite
+        // ends instead of the defaults of the collection
+        pub fn add_exact_interval(
+            &mut self,
+            dimension: u32,
+            direct_interval: Interval<T>,
+            generating_cycle: Option<G>,
+        ) {
+            self.persistence_invariant_descriptor.add_interval(
+                dimension,
+                direct_interval,
+                generating_cycle.unwrap_or_default(),
+            );
+        }
+
+        pub fn get_persistence_invariant_descriptor(
+            &self,
+        ) -> &PersistenceInvariantDescriptor<Interval<T>, G> {
+            return &self.persistence_invariant_descriptor;
+        }
+
+        // Sorted, unlike PersistenceInvariantDescriptor::get_dimensions, so that callers
+        // visiting every dimension do so deterministically
+        pub fn get_dimensions(&self) -> Vec<u32> {
+            let mut dimensions: Vec<u32> = self
+                .persistence_invariant_descriptor
+                .intervals
+                .keys()
+                .cloned()
+                .collect();
+            dimensions.sort_unstable();
+
+            return dimensions;
+        }
+
+        pub fn get_intervals_at_dimension(&self, dimension: u32) -> Vec<Interval<T>> {
+            return self
+                .persistence_invariant_descriptor
+                .intervals
+                .get(&dimension)
+                .cloned()
+                .unwrap_or_default();
+        }
+
+        pub fn get_generators_at_dimension(&self, dimension: u32) -> Vec<G> {
+            return self
+                .persistence_invariant_descriptor
+                .generators
+                .get(&dimension)
+                .cloned()
+                .unwrap_or_default();
+        }
+
+        pub fn get_interval_generator_pairs_at_dimension(
+            &self,
+            dimension: u32,
+        ) -> Vec<(Interval<T>, G)> {
+            return self
+                .persistence_invariant_descriptor
+                .interval_generator_pairs
+                .get(&dimension)
+                .cloned()
+                .unwrap_or_default();
+        }
+
+        // Sorts the barcode of every dimension, generators following their intervals
+        pub fn sort_intervals(&mut self, ordering: IntervalOrdering)
+        where
+            T: Sub<Output = T>,
+        {
+            self.sort_intervals_by(|a, b| ordering.compare(a, b));
+        }
+
+        pub fn sort_intervals_by<F>(&mut self, compare: F)
+        where
+            F: Fn(&Interval<T>, &Interval<T>) -> Ordering,
+        {
+            self.persistence_invariant_descriptor
+                .sort_intervals_by(compare);
+        }
     }
 
     impl<T, G> Default for AnnotatedBarcodeCollection<T, G>
@@ -164,6 +256,11 @@ pub mod barcode_collections {
         }
     }
 
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(bound(deserialize = "T: Clone + Deserialize<'de>, G: Clone + Deserialize<'de>"))
+    )]
     pub struct BarcodeCollection<T, G> {
         annotated_collection: AnnotatedBarcodeCollection<T, G>,
     }
@@ -197,6 +294,7 @@ pub mod barcode_collections {
                 .add_left_infinite_interval(dimension, end, None::<G>);
         }
 
+        // Re-adds the finite interval with the default endpoint flags of the collection
         pub fn add_direct_interval(&mut self, dimension: u32, direct_interval: Interval<T>) {
             self.add_interval(
                 dimension,
@@ -204,6 +302,39 @@ pub mod barcode_collections {
                 direct_interval.get_end().unwrap().clone(),
             );
         }
+
+        pub fn add_exact_interval(&mut self, dimension: u32, interval: Interval<T>) {
+            self.annotated_collection
+                .add_exact_interval(dimension, interval, None::<G>);
+        }
+
+        pub fn get_annotated_collection(&self) -> &AnnotatedBarcodeCollection<T, G> {
+            return &self.annotated_collection;
+        }
+
+        pub fn get_dimensions(&self) -> Vec<u32> {
+            return self.annotated_collection.get_dimensions();
+        }
+
+        pub fn get_intervals_at_dimension(&self, dimension: u32) -> Vec<Interval<T>> {
+            return self
+                .annotated_collection
+                .get_intervals_at_dimension(dimension);
+        }
+
+        pub fn sort_intervals(&mut self, ordering: IntervalOrdering)
+        where
+            T: Sub<Output = T>,
+        {
+            self.annotated_collection.sort_intervals(ordering);
+        }
+
+        pub fn sort_intervals_by<F>(&mut self, compare: F)
+        where
+            F: Fn(&Interval<T>, &Interval<T>) -> Ordering,
+        {
+            self.annotated_collection.sort_intervals_by(compare);
+        }
     }
 
     impl<T, G> Default for BarcodeCollection<T, G>
@@ -218,3 +349,112 @@ pub mod barcode_collections {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::barcode_collections::{AnnotatedBarcodeCollection, BarcodeCollection};
+    use crate::intervals::intervals::{Interval, IntervalOrdering};
+
+    #[test]
+    fn test_sorting_keeps_generators_aligned() {
+        let mut barcodes: AnnotatedBarcodeCollection<i32, u32> =
+            AnnotatedBarcodeCollection::default();
+        barcodes.add_right_infinite_interval(0, 0, Some(7));
+        barcodes.add_interval(0, 2, 3, Some(5));
+        barcodes.add_interval(0, 1, 5, Some(6));
+
+        barcodes.sort_intervals(IntervalOrdering::Persistence);
+        assert_eq!(
+            barcodes.get_interval_generator_pairs_at_dimension(0),
+            vec![
+                (Interval::finite_right_open_interval(2, 3), 5),
+                (Interval::finite_right_open_interval(1, 5), 6),
+                (Interval::right_infinite_right_open_interval(0), 7)
+            ]
+        );
+
+        barcodes.sort_intervals(IntervalOrdering::Birth);
+        assert_eq!(barcodes.get_generators_at_dimension(0), vec![7, 6, 5]);
+
+        barcodes.sort_intervals(IntervalOrdering::Death);
+        assert_eq!(barcodes.get_generators_at_dimension(0), vec![5, 6, 7]);
+    }
+
+    #[test]
+    fn test_direct_and_exact_intervals() {
+        let mut barcodes: BarcodeCollection<i32, ()> = BarcodeCollection::default();
+        barcodes.add_direct_interval(0, Interval::finite_closed_interval(1, 2));
+        barcodes.add_exact_interval(0, Interval::finite_closed_interval(1, 2));
+        barcodes.add_exact_interval(1, Interval::right_infinite_right_open_interval(3));
+
+        // Direct intervals take the default flags, exact ones keep their own
+        assert_eq!(
+            barcodes.get_intervals_at_dimension(0),
+            vec![
+                Interval::finite_right_open_interval(1, 2),
+                Interval::finite_closed_interval(1, 2)
+            ]
+        );
+        assert_eq!(
+            barcodes.get_intervals_at_dimension(1),
+            vec![Interval::right_infinite_right_open_interval(3)]
+        );
+    }
+
+    #[test]
+    fn test_sorting_plain_collections() {
+        let mut barcodes: BarcodeCollection<f64, ()> = BarcodeCollection::default();
+        barcodes.add_interval(1, 0.5, 0.75);
+        barcodes.add_left_infinite_interval(1, 2.0);
+        barcodes.add_interval(1, 0.25, 4.0);
+
+        barcodes.sort_intervals(IntervalOrdering::Lexicographic);
+        assert_eq!(
+            barcodes.get_intervals_at_dimension(1),
+            vec![
+                Interval::left_infinite_right_open_interval(2.0),
+                Interval::finite_right_open_interval(0.25, 4.0),
+                Interval::finite_right_open_interval(0.5, 0.75)
+            ]
+        );
+
+        barcodes.sort_intervals_by(|a, b| b.compare_by_death(a));
+        assert_eq!(
+            barcodes.get_intervals_at_dimension(1)[0],
+            Interval::finite_right_open_interval(0.25, 4.0)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde_round_trip() {
+        let mut barcodes: AnnotatedBarcodeCollection<f64, Vec<u32>> =
+            AnnotatedBarcodeCollection::default();
+        barcodes.add_interval(1, 0.5, 2.0, Some(vec![0, 1, 2]));
+        barcodes.add_right_infinite_interval(0, 0.0, Some(vec![0]));
+
+        let json: String = serde_json::to_string(&barcodes).unwrap();
+        let restored: AnnotatedBarcodeCollection<f64, Vec<u32>> =
+            serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored.get_dimensions(), vec![0, 1]);
+        assert_eq!(
+            restored.get_interval_generator_pairs_at_dimension(0),
+            vec![(Interval::right_infinite_right_open_interval(0.0), vec![0])]
+        );
+        assert_eq!(
+            restored.get_interval_generator_pairs_at_dimension(1),
+            barcodes.get_interval_generator_pairs_at_dimension(1)
+        );
+
+        let mut plain: BarcodeCollection<i32, ()> = BarcodeCollection::default();
+        plain.add_left_infinite_interval(2, 7);
+        let restored_plain: BarcodeCollection<i32, ()> =
+            serde_json::from_str(&serde_json::to_string(&plain).unwrap()).unwrap();
+
+        assert_eq!(
+            restored_plain.get_intervals_at_dimension(2),
+            vec![Interval::left_infinite_right_open_interval(7)]
+        );
+    }
+}
@@ -0,0 +1,437 @@
+#![allow(dead_code)]
+pub mod barcode_io {
+    use std::fmt::{Debug, Display};
+    use std::io::{BufRead, Write};
+    use std::str::FromStr;
+
+    use crate::barcode_collections::barcode_collections::{
+        AnnotatedBarcodeCollection, BarcodeCollection,
+    };
+    use crate::intervals::intervals::Interval;
+
+    // Text files hold one interval per line, "dimension interval" with the interval written
+    // like Interval's Display, e.g. "1 [0.5, inf)". Lines of three columns "dimension birth
+    // death", as written by Ripser or GUDHI, are read with the collection's default
+    // endpoints. CSV files have the header below, the last two columns being optional on
+    // input. Blank lines and lines starting with '#' are skipped. Generators are not stored.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum BarcodeFormat {
+        Text,
+        Csv,
+    }
+
+    const CSV_HEADER: &str = "dimension,birth,death,left_closed,right_closed";
+
+    #[derive(Debug)]
+    pub enum BarcodeReadError {
+        Io(std::io::Error),
+        Parse { line: usize, message: String },
+    }
+
+    impl Display for BarcodeReadError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                BarcodeReadError::Io(error) => write!(f, "Could not read barcodes: {}", error),
+                BarcodeReadError::Parse { line, message } => {
+                    write!(f, "Line {}: {}", line, message)
+                }
+            }
+        }
+    }
+
+    impl std::error::Error for BarcodeReadError {}
+
+    impl From<std::io::Error> for BarcodeReadError {
+        fn from(error: std::io::Error) -> Self {
+            BarcodeReadError::Io(error)
+        }
+    }
+
+    pub fn write_annotated_barcodes<T, G, W>(
+        barcodes: &AnnotatedBarcodeCollection<T, G>,
+        format: BarcodeFormat,
+        writer: &mut W,
+    ) -> std::io::Result<()>
+    where
+        T: Clone + Debug + Display + PartialEq + PartialOrd,
+        G: Clone + Debug + Default,
+        W: Write,
+    {
+        if format == BarcodeFormat::Csv {
+            writeln!(writer, "{}", CSV_HEADER)?;
+        }
+
+        for dimension in barcodes.get_dimensions() {
+            for interval in barcodes.get_intervals_at_dimension(dimension) {
+                let start: String = format_start(&interval);
+                let end: String = format_end(&interval);
+
+                match format {
+                    BarcodeFormat::Text => writeln!(
+                        writer,
+                        "{} {}{}, {}{}",
+                        dimension,
+                        if interval.is_left_closed() { "[" } else { "(" },
+                        start,
+                        end,
+                        if interval.is_right_closed() { "]" } else { ")" }
+                    )?,
+                    BarcodeFormat::Csv => writeln!(
+                        writer,
+                        "{},{},{},{},{}",
+                        dimension,
+                        start,
+                        end,
+                        interval.is_left_closed(),
+                        interval.is_right_closed()
+                    )?,
+                }
+            }
+        }
+
+        return Ok(());
+    }
+
+    pub fn write_barcodes<T, G, W>(
+        barcodes: &BarcodeCollection<T, G>,
+        format: BarcodeFormat,
+        writer: &mut W,
+    ) -> std::io::Result<()>
+    where
+        T: Clone + Debug + Display + PartialEq + PartialOrd,
+        G: Clone + Debug + Default,
+        W: Write,
+    {
+        return write_annotated_barcodes(barcodes.get_annotated_collection(), format, writer);
+    }
+
+    pub fn read_annotated_barcodes<T, G, R>(
+        reader: R,
+        format: BarcodeFormat,
+    ) -> Result<AnnotatedBarcodeCollection<T, G>, BarcodeReadError>
+    where
+        T: Clone + Debug + PartialEq + PartialOrd + FromStr,
+        G: Clone + Debug + Default,
+        R: BufRead,
+    {
+        let mut barcodes: AnnotatedBarcodeCollection<T, G> = AnnotatedBarcodeCollection::default();
+
+        for (index, line) in reader.lines().enumerate() {
+            let line: String = line?;
+            let content: &str = line.trim();
+
+            if content.is_empty()
+                || content.starts_with('#')
+                || (format == BarcodeFormat::Csv && content.starts_with("dimension"))
+            {
+                continue;
+            }
+
+            let parsed: Result<(), String> = match format {
+                BarcodeFormat::Text => parse_text_line(content, &mut barcodes),
+                BarcodeFormat::Csv => parse_csv_line(content, &mut barcodes),
+            };
+
+            parsed.map_err(|message| BarcodeReadError::Parse {
+                line: index + 1,
+                message,
+            })?;
+        }
+
+        return Ok(barcodes);
+    }
+
+    pub fn read_barcodes<T, G, R>(
+        reader: R,
+        format: BarcodeFormat,
+    ) -> Result<BarcodeCollection<T, G>, BarcodeReadError>
+    where
+        T: Clone + Debug + PartialEq + PartialOrd + FromStr,
+        G: Clone + Debug + Default,
+        R: BufRead,
+    {
+        return Ok(BarcodeCollection::new(read_annotated_barcodes(
+            reader, format,
+        )?));
+    }
+
+    fn format_start<T>(interval: &Interval<T>) -> String
+    where
+        T: Clone + Debug + Display + PartialEq + PartialOrd,
+    {
+        if interval.is_left_infinite() {
+            return String::from("-inf");
+        }
+
+        return interval.clone().get_start().unwrap().to_string();
+    }
+
+    fn format_end<T>(interval: &Interval<T>) -> String
+    where
+        T: Clone + Debug + Display + PartialEq + PartialOrd,
+    {
+        if interval.is_right_infinite() {
+            return String::from("inf");
+        }
+
+        return interval.clone().get_end().unwrap().to_string();
+    }
+
+    fn parse_dimension(token: &str) -> Result<u32, String> {
+        return token
+            .trim()
+            .parse::<u32>()
+            .map_err(|_| format!("Invalid dimension '{}'", token.trim()));
+    }
+
+    // None for an infinite end, which must lie on the expected side
+    fn parse_value<T>(token: &str, is_left: bool) -> Result<Option<T>, String>
+    where
+        T: FromStr,
+    {
+        let token: &str = token.trim();
+        let lowercase: String = token.to_lowercase();
+        let negative: bool = matches!(lowercase.as_str(), "-inf" | "-infinity");
+        let positive: bool = matches!(lowercase.as_str(), "inf" | "+inf" | "infinity");
+
+        if (is_left && negative) || (!is_left && positive) {
+            return Ok(None);
+        }
+
+        if negative || positive {
+            return Err(format!("'{}' is on the wrong end of the interval", token));
+        }
+
+        return token
+            .parse::<T>()
+            .map(Some)
+            .map_err(|_| format!("Invalid value '{}'", token));
+    }
+
+    fn parse_flag(token: &str) -> Result<bool, String> {
+        return token
+            .trim()
+            .parse::<bool>()
+            .map_err(|_| format!("Invalid endpoint flag '{}'", token.trim()));
+    }
+
+    fn add_parsed_interval<T, G>(
+        barcodes: &mut AnnotatedBarcodeCollection<T, G>,
+        dimension: u32,
+        start: Option<T>,
+        end: Option<T>,
+        flags: Option<(bool, bool)>,
+    ) where
+        T: Clone + Debug + PartialEq + PartialOrd,
+        G: Clone + Debug + Default,
+    {
+        let (is_left_infinite, is_right_infinite): (bool, bool) = (start.is_none(), end.is_none());
+
+        match (flags, start, end) {
+            (Some((is_left_closed, is_right_closed)), start, end) => barcodes.add_exact_interval(
+                dimension,
+                Interval::new(
+                    start,
+                    end,
+                    is_left_closed,
+                    is_right_closed,
+                    is_left_infinite,
+                    is_right_infinite,
+                ),
+                None,
+            ),
+            (None, Some(start), Some(end)) => barcodes.add_interval(dimension, start, end, None),
+            (None, Some(start), None) => {
+                barcodes.add_right_infinite_interval(dimension, start, None)
+            }
+            (None, None, Some(end)) => barcodes.add_left_infinite_interval(dimension, end, None),
+            (None, None, None) => barcodes.add_exact_interval(
+                dimension,
+                Interval::new(None, None, false, false, true, true),
+                None,
+            ),
+        }
+    }
+
+    fn parse_text_line<T, G>(
+        content: &str,
+        barcodes: &mut AnnotatedBarcodeCollection<T, G>,
+    ) -> Result<(), String>
+    where
+        T: Clone + Debug + PartialEq + PartialOrd + FromStr,
+        G: Clone + Debug + Default,
+    {
+        let (dimension_token, rest): (&str, &str) = content
+            .split_once(char::is_whitespace)
+            .ok_or_else(|| format!("Expected a dimension and an interval in '{}'", content))?;
+        let dimension: u32 = parse_dimension(dimension_token)?;
+        let rest: &str = rest.trim();
+
+        if !rest.starts_with(['[', '(']) {
+            let columns: Vec<&str> = rest.split_whitespace().collect();
+
+            if columns.len() != 2 {
+                return Err(format!("Expected a birth and a death in '{}'", content));
+            }
+
+            let start: Option<T> = parse_value(columns[0], true)?;
+            let end: Option<T> = parse_value(columns[1], false)?;
+            add_parsed_interval(barcodes, dimension, start, end, None);
+
+            return Ok(());
+        }
+
+        let is_left_closed: bool = rest.starts_with('[');
+        let is_right_closed: bool = match rest.chars().last() {
+            Some(']') => true,
+            Some(')') => false,
+            _ => return Err(format!("Unterminated interval '{}'", rest)),
+        };
+        let (start_token, end_token): (&str, &str) = rest[1..rest.len() - 1]
+            .split_once(',')
+            .ok_or_else(|| format!("Expected two endpoints in '{}'", rest))?;
+
+        let start: Option<T> = parse_value(start_token, true)?;
+        let end: Option<T> = parse_value(end_token, false)?;
+        add_parsed_interval(
+            barcodes,
+            dimension,
+            start,
+            end,
+            Some((is_left_closed, is_right_closed)),
+        );
+
+        return Ok(());
+    }
+
+    fn parse_csv_line<T, G>(
+        content: &str,
+        barcodes: &mut AnnotatedBarcodeCollection<T, G>,
+    ) -> Result<(), String>
+    where
+        T: Clone + Debug + PartialEq + PartialOrd + FromStr,
+        G: Clone + Debug + Default,
+    {
+        let fields: Vec<&str> = content.split(',').collect();
+
+        if fields.len() != 3 && fields.len() != 5 {
+            return Err(format!("Expected 3 or 5 fields, found {}", fields.len()));
+        }
+
+        let dimension: u32 = parse_dimension(fields[0])?;
+        let start: Option<T> = parse_value(fields[1], true)?;
+        let end: Option<T> = parse_value(fields[2], false)?;
+        let flags: Option<(bool, bool)> = if fields.len() == 5 {
+            Some((parse_flag(fields[3])?, parse_flag(fields[4])?))
+        } else {
+            None
+        };
+
+        add_parsed_interval(barcodes, dimension, start, end, flags);
+
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::barcode_io::{
+        read_annotated_barcodes, read_barcodes, write_annotated_barcodes, write_barcodes,
+        BarcodeFormat, BarcodeReadError,
+    };
+    use crate::barcode_collections::barcode_collections::{
+        AnnotatedBarcodeCollection, BarcodeCollection,
+    };
+    use crate::intervals::intervals::Interval;
+
+    fn barcodes() -> AnnotatedBarcodeCollection<f64, ()> {
+        let mut barcodes: AnnotatedBarcodeCollection<f64, ()> =
+            AnnotatedBarcodeCollection::default();
+        barcodes.add_right_infinite_interval(0, 0.0, None);
+        barcodes.add_interval(1, 0.5, 1.25, None);
+        barcodes.add_exact_interval(1, Interval::finite_left_open_interval(1.0, 2.0), None);
+        barcodes.add_exact_interval(2, Interval::left_infinite_closed_interval(3.0), None);
+
+        return barcodes;
+    }
+
+    #[test]
+    fn test_text_round_trip() {
+        let mut output: Vec<u8> = Vec::new();
+        write_annotated_barcodes(&barcodes(), BarcodeFormat::Text, &mut output).unwrap();
+        let text: String = String::from_utf8(output).unwrap();
+
+        assert_eq!(text, "0 [0, inf)\n1 [0.5, 1.25)\n1 (1, 2]\n2 [-inf, 3]\n");
+
+        let restored: AnnotatedBarcodeCollection<f64, ()> =
+            read_annotated_barcodes(text.as_bytes(), BarcodeFormat::Text).unwrap();
+
+        for dimension in 0..3 {
+            assert_eq!(
+                restored.get_intervals_at_dimension(dimension),
+                barcodes().get_intervals_at_dimension(dimension)
+            );
+        }
+    }
+
+    #[test]
+    fn test_csv_round_trip() {
+        let plain: BarcodeCollection<f64, ()> = BarcodeCollection::new(barcodes());
+        let mut output: Vec<u8> = Vec::new();
+        write_barcodes(&plain, BarcodeFormat::Csv, &mut output).unwrap();
+        let csv: String = String::from_utf8(output).unwrap();
+
+        assert!(
+            csv.starts_with("dimension,birth,death,left_closed,right_closed\n0,0,inf,true,false\n")
+        );
+
+        let restored: BarcodeCollection<f64, ()> =
+            read_barcodes(csv.as_bytes(), BarcodeFormat::Csv).unwrap();
+
+        assert_eq!(
+            restored.get_intervals_at_dimension(1),
+            plain.get_intervals_at_dimension(1)
+        );
+        assert_eq!(
+            restored.get_intervals_at_dimension(2),
+            vec![Interval::left_infinite_closed_interval(3.0)]
+        );
+    }
+
+    #[test]
+    fn test_reading_three_column_files() {
+        let text: &str = "# dimension birth death\n0 0 inf\n\n1 0.25 0.5\n";
+        let restored: BarcodeCollection<f64, ()> =
+            read_barcodes(text.as_bytes(), BarcodeFormat::Text).unwrap();
+
+        assert_eq!(
+            restored.get_intervals_at_dimension(0),
+            vec![Interval::right_infinite_right_open_interval(0.0)]
+        );
+        assert_eq!(
+            restored.get_intervals_at_dimension(1),
+            vec![Interval::finite_right_open_interval(0.25, 0.5)]
+        );
+
+        let csv: &str = "dimension,birth,death\n3,1,4\n";
+        let integers: BarcodeCollection<u32, ()> =
+            read_barcodes(csv.as_bytes(), BarcodeFormat::Csv).unwrap();
+        assert_eq!(
+            integers.get_intervals_at_dimension(3),
+            vec![Interval::finite_right_open_interval(1, 4)]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors_report_lines() {
+        let text: &str = "0 [0, 1)\n1 [inf, 2)\n";
+
+        match read_barcodes::<f64, (), _>(text.as_bytes(), BarcodeFormat::Text) {
+            Err(BarcodeReadError::Parse { line, .. }) => assert_eq!(line, 2),
+            _ => panic!("Expected a parse error"),
+        }
+
+        assert!(read_barcodes::<f64, (), _>("x,0,1\n".as_bytes(), BarcodeFormat::Csv).is_err());
+        assert!(read_barcodes::<f64, (), _>("1 [0, 1\n".as_bytes(), BarcodeFormat::Text).is_err());
+    }
+}
@@ -0,0 +1,343 @@
+#![allow(dead_code)]
+pub mod betti_curves {
+    use std::cmp::Ordering;
+    use std::collections::BTreeMap;
+    use std::fmt::Debug;
+
+    use crate::intervals::intervals::Interval;
+    use crate::persistence_invariant_descriptor::persistence_invariant_descriptor::PersistenceInvariantDescriptor;
+
+    // Piecewise constant function of the filtration parameter. The values at the breakpoints
+    // are kept apart from the values on the open segments between them, so that a function
+    // can jump at a point (e.g. at the closed end of an interval) and back right after it.
+    // segment_values[i] is the value just before breakpoints[i], and the last one the value
+    // after the final breakpoint.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct StepFunction<T, V> {
+        breakpoints: Vec<T>,
+        point_values: Vec<V>,
+        segment_values: Vec<V>,
+    }
+
+    impl<T, V> StepFunction<T, V>
+    where
+        T: Clone + Debug + PartialOrd,
+        V: Clone + Debug,
+    {
+        pub fn new(breakpoints: Vec<T>, point_values: Vec<V>, segment_values: Vec<V>) -> Self {
+            assert_eq!(
+                breakpoints.len(),
+                point_values.len(),
+                "Every breakpoint needs a value"
+            );
+            assert_eq!(
+                breakpoints.len() + 1,
+                segment_values.len(),
+                "Every segment between breakpoints needs a value"
+            );
+
+            Self {
+                breakpoints,
+                point_values,
+                segment_values,
+            }
+        }
+
+        pub fn get_breakpoints(&self) -> &[T] {
+            return &self.breakpoints;
+        }
+
+        pub fn get_point_values(&self) -> &[V] {
+            return &self.point_values;
+        }
+
+        pub fn get_segment_values(&self) -> &[V] {
+            return &self.segment_values;
+        }
+
+        pub fn evaluate(&self, point: &T) -> V {
+            let index: usize = self.breakpoints.partition_point(|b| b < point);
+
+            if index < self.breakpoints.len() && self.breakpoints[index] == *point {
+                return self.point_values[index].clone();
+            }
+
+            return self.segment_values[index].clone();
+        }
+
+        pub fn map<W, F>(&self, function: F) -> StepFunction<T, W>
+        where
+            F: Fn(&V) -> W,
+        {
+            return StepFunction {
+                breakpoints: self.breakpoints.clone(),
+                point_values: self.point_values.iter().map(&function).collect(),
+                segment_values: self.segment_values.iter().map(&function).collect(),
+            };
+        }
+    }
+
+    // Sum of the weights of the intervals alive at every point. Membership of a breakpoint
+    // follows Interval::contains_point, and an interval covers the open segment after a
+    // breakpoint exactly when it starts no later than it and ends no earlier than the next.
+    // Intervals with an endpoint that is not comparable to itself, like NaN, are skipped.
+    fn count_intervals<T>(weighted_intervals: &[(&Interval<T>, i64)]) -> StepFunction<T, i64>
+    where
+        T: Clone + Debug + PartialEq + PartialOrd,
+    {
+        let is_ordered = |value: Option<T>| value.is_none_or(|v| v.partial_cmp(&v).is_some());
+        let weighted_intervals: Vec<(&Interval<T>, i64)> = weighted_intervals
+            .iter()
+            .filter(|(interval, _)| {
+                let interval: &Interval<T> = interval;
+                is_ordered(interval.clone().get_start()) && is_ordered(interval.clone().get_end())
+            })
+            .cloned()
+            .collect();
+        let mut breakpoints: Vec<T> = Vec::new();
+
+        for (interval, _) in weighted_intervals.iter() {
+            let interval: &Interval<T> = interval;
+            breakpoints.extend(interval.clone().get_start());
+            breakpoints.extend(interval.clone().get_end());
+        }
+
+        // Every remaining endpoint is comparable
+        breakpoints.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        breakpoints.dedup();
+
+        let point_values: Vec<i64> = breakpoints
+            .iter()
+            .map(|b| {
+                weighted_intervals
+                    .iter()
+                    .filter(|(interval, _)| interval.contains_point(b.clone()))
+                    .map(|(_, weight)| weight)
+                    .sum()
+            })
+            .collect();
+
+        let segment_values: Vec<i64> = (0..=breakpoints.len())
+            .map(|i| {
+                weighted_intervals
+                    .iter()
+                    .filter(|(interval, _)| {
+                        let interval: &Interval<T> = interval;
+                        let covers_left: bool = interval.is_left_infinite()
+                            || (i > 0
+                                && interval.clone().get_start().unwrap() <= breakpoints[i - 1]);
+                        let covers_right: bool = interval.is_right_infinite()
+                            || (i < breakpoints.len()
+                                && interval.clone().get_end().unwrap() >= breakpoints[i]);
+
+                        covers_left && covers_right
+                    })
+                    .map(|(_, weight)| weight)
+                    .sum()
+            })
+            .collect();
+
+        return StepFunction::new(breakpoints, point_values, segment_values);
+    }
+
+    // The Betti number in the given dimension as a function of the filtration parameter
+    pub fn compute_betti_curve<T, G>(
+        descriptor: &PersistenceInvariantDescriptor<Interval<T>, G>,
+        dimension: u32,
+    ) -> StepFunction<T, usize>
+    where
+        T: Clone + Debug + PartialEq + PartialOrd,
+    {
+        let weighted_intervals: Vec<(&Interval<T>, i64)> = descriptor
+            .intervals
+            .get(&dimension)
+            .map_or(Vec::new(), |intervals| {
+                intervals.iter().map(|i| (i, 1)).collect()
+            });
+
+        return count_intervals(&weighted_intervals).map(|value| *value as usize);
+    }
+
+    // Betti curves of every dimension of the descriptor
+    pub fn compute_betti_curves<T, G>(
+        descriptor: &PersistenceInvariantDescriptor<Interval<T>, G>,
+    ) -> BTreeMap<u32, StepFunction<T, usize>>
+    where
+        T: Clone + Debug + PartialEq + PartialOrd,
+    {
+        return descriptor
+            .intervals
+            .keys()
+            .map(|dimension| (*dimension, compute_betti_curve(descriptor, *dimension)))
+            .collect();
+    }
+
+    // The alternating sum of the Betti curves, sum_d (-1)^d beta_d
+    pub fn compute_euler_characteristic_curve<T, G>(
+        descriptor: &PersistenceInvariantDescriptor<Interval<T>, G>,
+    ) -> StepFunction<T, i64>
+    where
+        T: Clone + Debug + PartialEq + PartialOrd,
+    {
+        let weighted_intervals: Vec<(&Interval<T>, i64)> = descriptor
+            .intervals
+            .iter()
+            .flat_map(|(dimension, intervals)| {
+                let sign: i64 = if dimension.is_multiple_of(2) { 1 } else { -1 };
+                intervals.iter().map(move |i| (i, sign))
+            })
+            .collect();
+
+        return count_intervals(&weighted_intervals);
+    }
+
+    // Number of infinite intervals in every dimension, i.e. the Betti numbers of the final
+    // complex, analogue of JavaPlex's getBettiNumbersMap
+    pub fn get_betti_numbers<T, G>(
+        descriptor: &PersistenceInvariantDescriptor<Interval<T>, G>,
+    ) -> BTreeMap<u32, usize>
+    where
+        T: Clone + Debug + PartialEq + PartialOrd,
+    {
+        return descriptor
+            .intervals
+            .iter()
+            .map(|(dimension, intervals)| {
+                (
+                    *dimension,
+                    intervals.iter().filter(|i| i.is_right_infinite()).count(),
+                )
+            })
+            .collect();
+    }
+
+    // The Betti numbers as a sequence indexed by dimension, from 0 up to the largest
+    // dimension present, analogue of JavaPlex's getBettiSequence
+    pub fn get_betti_sequence<T, G>(
+        descriptor: &PersistenceInvariantDescriptor<Interval<T>, G>,
+    ) -> Vec<usize>
+    where
+        T: Clone + Debug + PartialEq + PartialOrd,
+    {
+        let betti_numbers: BTreeMap<u32, usize> = get_betti_numbers(descriptor);
+
+        return match betti_numbers.keys().last() {
+            Some(max_dimension) => (0..=*max_dimension)
+                .map(|d| betti_numbers.get(&d).cloned().unwrap_or(0))
+                .collect(),
+            None => Vec::new(),
+        };
+    }
+
+    // The Betti numbers of every dimension at a single filtration value
+    pub fn get_betti_numbers_at<T, G>(
+        descriptor: &PersistenceInvariantDescriptor<Interval<T>, G>,
+        filtration_value: T,
+    ) -> BTreeMap<u32, usize>
+    where
+        T: Clone + Debug + PartialEq + PartialOrd,
+    {
+        return descriptor
+            .intervals
+            .iter()
+            .map(|(dimension, intervals)| {
+                (
+                    *dimension,
+                    intervals
+                        .iter()
+                        .filter(|i| i.contains_point(filtration_value.clone()))
+                        .count(),
+                )
+            })
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::betti_curves::{
+        compute_betti_curve, compute_betti_curves, compute_euler_characteristic_curve,
+        get_betti_numbers, get_betti_numbers_at, get_betti_sequence, StepFunction,
+    };
+    use crate::intervals::intervals::Interval;
+    use crate::persistence_invariant_descriptor::persistence_invariant_descriptor::PersistenceInvariantDescriptor;
+
+    fn descriptor() -> PersistenceInvariantDescriptor<Interval<f64>, ()> {
+        let mut descriptor: PersistenceInvariantDescriptor<Interval<f64>, ()> =
+            PersistenceInvariantDescriptor::new();
+        descriptor.add_interval(0, Interval::right_infinite_right_open_interval(0.0), ());
+        descriptor.add_interval(0, Interval::finite_right_open_interval(0.0, 1.0), ());
+        descriptor.add_interval(1, Interval::finite_right_open_interval(1.0, 2.0), ());
+
+        return descriptor;
+    }
+
+    #[test]
+    fn test_betti_curves_respect_endpoints() {
+        let mut descriptor: PersistenceInvariantDescriptor<Interval<f64>, ()> = descriptor();
+        descriptor.add_interval(1, Interval::finite_left_open_interval(2.0, 3.0), ());
+
+        let betti_zero: StepFunction<f64, usize> = compute_betti_curve(&descriptor, 0);
+        let betti_one: StepFunction<f64, usize> = compute_betti_curve(&descriptor, 1);
+
+        assert_eq!(betti_zero.get_breakpoints(), &[0.0, 1.0]);
+        assert_eq!(betti_zero.evaluate(&-1.0), 0);
+        assert_eq!(betti_zero.evaluate(&0.0), 2);
+        assert_eq!(betti_zero.evaluate(&0.5), 2);
+        assert_eq!(betti_zero.evaluate(&1.0), 1);
+        assert_eq!(betti_zero.evaluate(&100.0), 1);
+
+        // [1, 2) and (2, 3] leave a gap at 2
+        assert_eq!(betti_one.evaluate(&1.5), 1);
+        assert_eq!(betti_one.evaluate(&2.0), 0);
+        assert_eq!(betti_one.evaluate(&2.5), 1);
+        assert_eq!(betti_one.evaluate(&3.0), 1);
+        assert_eq!(betti_one.evaluate(&3.5), 0);
+        assert_eq!(compute_betti_curves(&descriptor).len(), 2);
+        assert!(compute_betti_curve(&descriptor, 4)
+            .get_breakpoints()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_nan_endpoints_are_skipped() {
+        let mut with_nan: PersistenceInvariantDescriptor<Interval<f64>, ()> = descriptor();
+        with_nan.add_interval(0, Interval::finite_right_open_interval(f64::NAN, 1.0), ());
+        with_nan.add_interval(
+            0,
+            Interval::right_infinite_right_open_interval(f64::NAN),
+            (),
+        );
+
+        assert_eq!(
+            compute_betti_curve(&with_nan, 0),
+            compute_betti_curve(&descriptor(), 0)
+        );
+    }
+
+    #[test]
+    fn test_euler_characteristic_curve() {
+        let euler: StepFunction<f64, i64> = compute_euler_characteristic_curve(&descriptor());
+
+        assert_eq!(euler.evaluate(&0.5), 2);
+        assert_eq!(euler.evaluate(&1.0), 0);
+        assert_eq!(euler.evaluate(&1.5), 0);
+        assert_eq!(euler.evaluate(&2.0), 1);
+        assert_eq!(euler.get_segment_values(), &[0, 2, 0, 1]);
+    }
+
+    #[test]
+    fn test_betti_numbers_and_sequence() {
+        let mut descriptor: PersistenceInvariantDescriptor<Interval<f64>, ()> = descriptor();
+        descriptor.add_interval(2, Interval::right_infinite_right_open_interval(3.0), ());
+
+        assert_eq!(get_betti_sequence(&descriptor), vec![1, 0, 1]);
+        assert_eq!(get_betti_numbers(&descriptor).get(&2), Some(&1));
+        assert_eq!(
+            get_betti_numbers_at(&descriptor, 1.5)
+                .into_values()
+                .collect::<Vec<usize>>(),
+            vec![1, 1, 0]
+        );
+    }
+}
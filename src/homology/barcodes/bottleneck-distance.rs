@@ -0,0 +1,549 @@
+#![allow(dead_code)]
+pub mod bottleneck_distance {
+    use std::collections::VecDeque;
+    use std::fmt::Debug;
+
+    use crate::barcode_collections::barcode_collections::{
+        AnnotatedBarcodeCollection, BarcodeCollection,
+    };
+    use crate::intervals::intervals::Interval;
+
+    // One pair of a matching, None standing for the diagonal
+    pub type MatchedPair<T> = (Option<Interval<T>>, Option<Interval<T>>);
+
+    // A matching between two persistence diagrams together with its cost. An interval paired
+    // with None is matched to the diagonal, or left unmatched when it is infinite.
+    #[derive(Clone)]
+    pub struct DiagramMatching<T> {
+        distance: f64,
+        matched_pairs: Vec<MatchedPair<T>>,
+    }
+
+    impl<T> DiagramMatching<T> {
+        pub fn new(distance: f64, matched_pairs: Vec<MatchedPair<T>>) -> Self {
+            Self {
+                distance,
+                matched_pairs,
+            }
+        }
+
+        pub fn get_distance(&self) -> f64 {
+            return self.distance;
+        }
+
+        pub fn get_matched_pairs(&self) -> &[MatchedPair<T>] {
+            return &self.matched_pairs;
+        }
+    }
+
+    impl<T> Debug for DiagramMatching<T>
+    where
+        T: Clone + Debug + PartialEq + PartialOrd,
+    {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            return f
+                .debug_struct("DiagramMatching")
+                .field("distance", &self.distance)
+                .field("matched_pairs", &self.matched_pairs)
+                .finish();
+        }
+    }
+
+    // The intervals of one diagram sorted by which of their ends are infinite. Every entry
+    // keeps the index of its interval; finite points are (index, birth, death), and the
+    // essential classes only keep their finite end. Intervals infinite on the right can only
+    // be matched with each other at finite cost, and likewise for the other two kinds.
+    #[derive(Debug, Clone, Default)]
+    pub struct DiagramPoints {
+        pub finite: Vec<(usize, f64, f64)>,
+        pub right_infinite: Vec<(usize, f64)>,
+        pub left_infinite: Vec<(usize, f64)>,
+        pub doubly_infinite: Vec<usize>,
+    }
+
+    impl DiagramPoints {
+        pub fn new<T>(intervals: &[Interval<T>]) -> Self
+        where
+            T: Clone + Debug + PartialOrd + Into<f64>,
+        {
+            let mut points: DiagramPoints = DiagramPoints::default();
+
+            for (index, interval) in intervals.iter().enumerate() {
+                let start: Option<f64> = interval.clone().get_start().map(|s| s.into());
+                let end: Option<f64> = interval.clone().get_end().map(|e| e.into());
+
+                match (interval.is_left_infinite(), interval.is_right_infinite()) {
+                    (false, false) => points.finite.push((index, start.unwrap(), end.unwrap())),
+                    (false, true) => points.right_infinite.push((index, start.unwrap())),
+                    (true, false) => points.left_infinite.push((index, end.unwrap())),
+                    (true, true) => points.doubly_infinite.push(index),
+                }
+            }
+
+            // Sorting the finite ends pairs the essential classes optimally for any L^q cost
+            points
+                .right_infinite
+                .sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+            points
+                .left_infinite
+                .sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+
+            return points;
+        }
+
+        pub fn has_matching_essential_classes(&self, other: &DiagramPoints) -> bool {
+            return self.right_infinite.len() == other.right_infinite.len()
+                && self.left_infinite.len() == other.left_infinite.len()
+                && self.doubly_infinite.len() == other.doubly_infinite.len();
+        }
+
+        // Pairs of essential classes in sorted order, each with the distance between their
+        // finite ends. Surplus classes on either side are paired with None.
+        pub fn match_essential_classes(
+            &self,
+            other: &DiagramPoints,
+        ) -> Vec<(Option<usize>, Option<usize>, f64)> {
+            let mut pairs: Vec<(Option<usize>, Option<usize>, f64)> = Vec::new();
+
+            for (left, right) in [
+                (&self.right_infinite, &other.right_infinite),
+                (&self.left_infinite, &other.left_infinite),
+            ] {
+                for k in 0..left.len().max(right.len()) {
+                    pairs.push(match (left.get(k), right.get(k)) {
+                        (Some(a), Some(b)) => (Some(a.0), Some(b.0), (a.1 - b.1).abs()),
+                        (a, b) => (a.map(|a| a.0), b.map(|b| b.0), f64::INFINITY),
+                    });
+                }
+            }
+
+            let left: &Vec<usize> = &self.doubly_infinite;
+            let right: &Vec<usize> = &other.doubly_infinite;
+            for k in 0..left.len().max(right.len()) {
+                let cost: f64 = if k < left.len() && k < right.len() {
+                    0.0
+                } else {
+                    f64::INFINITY
+                };
+
+                pairs.push((left.get(k).cloned(), right.get(k).cloned(), cost));
+            }
+
+            return pairs;
+        }
+    }
+
+    // Bottleneck distance between the diagrams of the two collections in the given dimension
+    pub fn compute_bottleneck_distance<T, G>(
+        first: &BarcodeCollection<T, G>,
+        second: &BarcodeCollection<T, G>,
+        dimension: u32,
+    ) -> DiagramMatching<T>
+    where
+        T: Clone + Debug + PartialEq + PartialOrd + Into<f64>,
+        G: Clone + Debug + Default,
+    {
+        return compute_bottleneck_distance_of_intervals(
+            &first.get_intervals_at_dimension(dimension),
+            &second.get_intervals_at_dimension(dimension),
+        );
+    }
+
+    pub fn compute_annotated_bottleneck_distance<T, G>(
+        first: &AnnotatedBarcodeCollection<T, G>,
+        second: &AnnotatedBarcodeCollection<T, G>,
+        dimension: u32,
+    ) -> DiagramMatching<T>
+    where
+        T: Clone + Debug + PartialEq + PartialOrd + Into<f64>,
+        G: Clone + Debug + Default,
+    {
+        return compute_bottleneck_distance_of_intervals(
+            &first.get_intervals_at_dimension(dimension),
+            &second.get_intervals_at_dimension(dimension),
+        );
+    }
+
+    // The smallest eps such that the diagrams have a matching moving no point more than eps
+    // in the L-infinity norm, where points may also be sent to the diagonal. The optimal eps
+    // is one of the pairwise costs, so we binary search over those and test each candidate
+    // for a perfect matching. The distance is infinite when the essential classes of the two
+    // diagrams cannot be paired up.
+    pub fn compute_bottleneck_distance_of_intervals<T>(
+        first: &[Interval<T>],
+        second: &[Interval<T>],
+    ) -> DiagramMatching<T>
+    where
+        T: Clone + Debug + PartialOrd + Into<f64>,
+    {
+        let first_points: DiagramPoints = DiagramPoints::new(first);
+        let second_points: DiagramPoints = DiagramPoints::new(second);
+        let graph: BottleneckGraph = BottleneckGraph::new(&first_points, &second_points);
+
+        let mut candidates: Vec<f64> = graph.get_candidate_distances();
+        candidates.sort_by(f64::total_cmp);
+        candidates.dedup();
+
+        let mut finite_distance: f64 = 0.0;
+
+        if !candidates.is_empty() {
+            let (mut low, mut high): (usize, usize) = (0, candidates.len() - 1);
+
+            while low < high {
+                let middle: usize = (low + high) / 2;
+
+                if graph.perfect_matching(candidates[middle]).is_some() {
+                    high = middle;
+                } else {
+                    low = middle + 1;
+                }
+            }
+
+            finite_distance = candidates[low];
+        }
+
+        let finite_matching: Vec<Option<usize>> = graph.perfect_matching(finite_distance).unwrap();
+
+        let mut distance: f64 = finite_distance;
+        let mut matched_pairs: Vec<MatchedPair<T>> = Vec::new();
+
+        for (a, b, cost) in first_points.match_essential_classes(&second_points) {
+            distance = distance.max(cost);
+            matched_pairs.push((a.map(|a| first[a].clone()), b.map(|b| second[b].clone())));
+        }
+
+        for (a, b) in graph.get_matched_pairs(&finite_matching) {
+            matched_pairs.push((a.map(|a| first[a].clone()), b.map(|b| second[b].clone())));
+        }
+
+        return DiagramMatching::new(distance, matched_pairs);
+    }
+
+    // Bipartite graph used for the finite points. The left side holds the points of the first
+    // diagram followed by the diagonal projections of the points of the second one, and the
+    // right side the points of the second diagram followed by the projections of the first.
+    // Projections are matched to each other at no cost, so a perfect matching of the graph is
+    // exactly a partial matching of the diagrams with the rest sent to the diagonal.
+    struct BottleneckGraph {
+        first: Vec<(usize, f64, f64)>,
+        second: Vec<(usize, f64, f64)>,
+    }
+
+    impl BottleneckGraph {
+        fn new(first: &DiagramPoints, second: &DiagramPoints) -> Self {
+            Self {
+                first: first.finite.clone(),
+                second: second.finite.clone(),
+            }
+        }
+
+        fn point_distance(a: &(usize, f64, f64), b: &(usize, f64, f64)) -> f64 {
+            return (a.1 - b.1).abs().max((a.2 - b.2).abs());
+        }
+
+        fn diagonal_distance(a: &(usize, f64, f64)) -> f64 {
+            return (a.2 - a.1) / 2.0;
+        }
+
+        fn get_candidate_distances(&self) -> Vec<f64> {
+            let mut candidates: Vec<f64> = Vec::new();
+
+            for a in self.first.iter() {
+                candidates.push(Self::diagonal_distance(a));
+
+                for b in self.second.iter() {
+                    candidates.push(Self::point_distance(a, b));
+                }
+            }
+
+            candidates.extend(self.second.iter().map(Self::diagonal_distance));
+
+            return candidates;
+        }
+
+        fn adjacency(&self, threshold: f64) -> Vec<Vec<usize>> {
+            let (n, m): (usize, usize) = (self.first.len(), self.second.len());
+            let mut adjacency: Vec<Vec<usize>> = Vec::with_capacity(n + m);
+
+            for (i, a) in self.first.iter().enumerate() {
+                let mut neighbors: Vec<usize> = (0..m)
+                    .filter(|j| Self::point_distance(a, &self.second[*j]) <= threshold)
+                    .collect();
+
+                if Self::diagonal_distance(a) <= threshold {
+                    neighbors.push(m + i);
+                }
+
+                adjacency.push(neighbors);
+            }
+
+            for (j, b) in self.second.iter().enumerate() {
+                let mut neighbors: Vec<usize> = Vec::with_capacity(n + 1);
+
+                if Self::diagonal_distance(b) <= threshold {
+                    neighbors.push(j);
+                }
+
+                neighbors.extend(m..m + n);
+                adjacency.push(neighbors);
+            }
+
+            return adjacency;
+        }
+
+        // The match of every left vertex, if all of them can be matched within the threshold
+        fn perfect_matching(&self, threshold: f64) -> Option<Vec<Option<usize>>> {
+            let adjacency: Vec<Vec<usize>> = self.adjacency(threshold);
+            let mut matcher: HopcroftKarp =
+                HopcroftKarp::new(&adjacency, self.first.len() + self.second.len());
+            let size: usize = matcher.maximum_matching();
+
+            if size < adjacency.len() {
+                return None;
+            }
+
+            return Some(matcher.match_left);
+        }
+
+        // Translates a perfect matching back to interval indices, dropping the pairs of
+        // diagonal projections
+        fn get_matched_pairs(
+            &self,
+            matching: &[Option<usize>],
+        ) -> Vec<(Option<usize>, Option<usize>)> {
+            let (n, m): (usize, usize) = (self.first.len(), self.second.len());
+            let mut pairs: Vec<(Option<usize>, Option<usize>)> = Vec::new();
+
+            for (u, v) in matching.iter().enumerate() {
+                let v: usize = v.unwrap();
+
+                if u < n && v < m {
+                    pairs.push((Some(self.first[u].0), Some(self.second[v].0)));
+                } else if u < n {
+                    pairs.push((Some(self.first[u].0), None));
+                } else if v < m {
+                    pairs.push((None, Some(self.second[v].0)));
+                }
+            }
+
+            return pairs;
+        }
+    }
+
+    // Hopcroft-Karp maximum bipartite matching, alternating breadth first layering with
+    // depth first augmentation along shortest paths
+    struct HopcroftKarp<'a> {
+        adjacency: &'a [Vec<usize>],
+        match_left: Vec<Option<usize>>,
+        match_right: Vec<Option<usize>>,
+        layers: Vec<usize>,
+    }
+
+    impl<'a> HopcroftKarp<'a> {
+        fn new(adjacency: &'a [Vec<usize>], num_right: usize) -> Self {
+            Self {
+                adjacency,
+                match_left: vec![None; adjacency.len()],
+                match_right: vec![None; num_right],
+                layers: vec![usize::MAX; adjacency.len()],
+            }
+        }
+
+        fn maximum_matching(&mut self) -> usize {
+            let mut size: usize = 0;
+
+            while self.layer_free_vertices() {
+                for u in 0..self.adjacency.len() {
+                    if self.match_left[u].is_none() && self.augment(u) {
+                        size += 1;
+                    }
+                }
+            }
+
+            return size;
+        }
+
+        // Returns whether some augmenting path exists
+        fn layer_free_vertices(&mut self) -> bool {
+            let mut queue: VecDeque<usize> = VecDeque::new();
+            let mut found: bool = false;
+
+            for u in 0..self.adjacency.len() {
+                if self.match_left[u].is_none() {
+                    self.layers[u] = 0;
+                    queue.push_back(u);
+                } else {
+                    self.layers[u] = usize::MAX;
+                }
+            }
+
+            while let Some(u) = queue.pop_front() {
+                for v in self.adjacency[u].iter() {
+                    match self.match_right[*v] {
+                        None => found = true,
+                        Some(w) => {
+                            if self.layers[w] == usize::MAX {
+                                self.layers[w] = self.layers[u] + 1;
+                                queue.push_back(w);
+                            }
+                        }
+                    }
+                }
+            }
+
+            return found;
+        }
+
+        // Depth first search along the layers from the free vertex root, with an explicit
+        // stack of (left vertex, index of its next edge) so long paths cannot overflow
+        fn augment(&mut self, root: usize) -> bool {
+            let mut stack: Vec<(usize, usize)> = vec![(root, 0)];
+
+            while let Some(&(u, k)) = stack.last() {
+                if k == self.adjacency[u].len() {
+                    // Dead end, no augmenting path goes through u in this phase
+                    self.layers[u] = usize::MAX;
+                    stack.pop();
+                    continue;
+                }
+
+                stack.last_mut().unwrap().1 += 1;
+                let v: usize = self.adjacency[u][k];
+
+                match self.match_right[v] {
+                    None => {
+                        // Flip the path, every vertex on the stack takes the edge it last tried
+                        for (w, next_edge) in stack.iter() {
+                            let x: usize = self.adjacency[*w][next_edge - 1];
+                            self.match_left[*w] = Some(x);
+                            self.match_right[x] = Some(*w);
+                        }
+
+                        return true;
+                    }
+                    Some(w) => {
+                        if self.layers[w] == self.layers[u] + 1 {
+                            stack.push((w, 0));
+                        }
+                    }
+                }
+            }
+
+            return false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::bottleneck_distance::{
+        compute_annotated_bottleneck_distance, compute_bottleneck_distance,
+        compute_bottleneck_distance_of_intervals, DiagramMatching,
+    };
+    use crate::barcode_collections::barcode_collections::{
+        AnnotatedBarcodeCollection, BarcodeCollection,
+    };
+    use crate::intervals::intervals::Interval;
+
+    #[test]
+    fn test_bottleneck_distance_of_finite_diagrams() {
+        let mut first: BarcodeCollection<f64, ()> = BarcodeCollection::default();
+        let mut second: BarcodeCollection<f64, ()> = BarcodeCollection::default();
+        first.add_interval(1, 0.0, 2.0);
+        first.add_interval(1, 1.0, 1.2);
+        second.add_interval(1, 0.0, 2.5);
+        second.add_interval(0, 0.0, 10.0);
+
+        let matching: DiagramMatching<f64> = compute_bottleneck_distance(&first, &second, 1);
+
+        // The long bars move by 0.5 and the short one goes to the diagonal at cost 0.1
+        assert_eq!(matching.get_distance(), 0.5);
+        assert_eq!(matching.get_matched_pairs().len(), 2);
+        assert!(matching.get_matched_pairs().contains(&(
+            Some(Interval::finite_right_open_interval(0.0, 2.0)),
+            Some(Interval::finite_right_open_interval(0.0, 2.5))
+        )));
+        assert!(matching
+            .get_matched_pairs()
+            .contains(&(Some(Interval::finite_right_open_interval(1.0, 1.2)), None)));
+
+        assert_eq!(
+            compute_bottleneck_distance(&first, &first, 1).get_distance(),
+            0.0
+        );
+        assert_eq!(
+            compute_bottleneck_distance(&first, &second, 0).get_distance(),
+            5.0
+        );
+    }
+
+    #[test]
+    fn test_bottleneck_distance_of_large_staircases() {
+        // Every bar overlaps its neighbours, so the matching is found along long chains of
+        // alternating edges
+        let mut first: BarcodeCollection<f64, ()> = BarcodeCollection::default();
+        let mut second: BarcodeCollection<f64, ()> = BarcodeCollection::default();
+        for i in 0..200 {
+            first.add_interval(1, i as f64, i as f64 + 100.0);
+            second.add_interval(1, (199 - i) as f64 + 0.5, (199 - i) as f64 + 100.5);
+        }
+
+        let matching: DiagramMatching<f64> = compute_bottleneck_distance(&first, &second, 1);
+
+        assert_eq!(matching.get_distance(), 0.5);
+        assert_eq!(matching.get_matched_pairs().len(), 200);
+        assert!(matching
+            .get_matched_pairs()
+            .iter()
+            .all(|(a, b)| a.is_some() && b.is_some()));
+    }
+
+    #[test]
+    fn test_diagonal_matching_beats_crossing() {
+        // Matching the two points to each other would cost 3, sending both to the diagonal
+        // costs half the longer persistence
+        let first: Vec<Interval<i32>> = vec![Interval::finite_right_open_interval(0, 2)];
+        let second: Vec<Interval<i32>> = vec![Interval::finite_right_open_interval(3, 4)];
+
+        let matching: DiagramMatching<i32> =
+            compute_bottleneck_distance_of_intervals(&first, &second);
+
+        assert_eq!(matching.get_distance(), 1.0);
+        assert_eq!(matching.get_matched_pairs().len(), 2);
+        assert_eq!(
+            compute_bottleneck_distance_of_intervals::<i32>(&[], &[]).get_distance(),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_bottleneck_distance_with_infinite_intervals() {
+        let mut first: AnnotatedBarcodeCollection<f64, ()> = AnnotatedBarcodeCollection::default();
+        let mut second: AnnotatedBarcodeCollection<f64, ()> = AnnotatedBarcodeCollection::default();
+        first.add_right_infinite_interval(0, 0.0, None);
+        first.add_right_infinite_interval(0, 1.0, None);
+        first.add_interval(0, 0.0, 0.4, None);
+        second.add_right_infinite_interval(0, 3.0, None);
+        second.add_right_infinite_interval(0, 0.5, None);
+
+        let matching: DiagramMatching<f64> =
+            compute_annotated_bottleneck_distance(&first, &second, 0);
+
+        // Essential classes are paired by birth
+        assert_eq!(matching.get_distance(), 2.0);
+        assert!(matching.get_matched_pairs().contains(&(
+            Some(Interval::right_infinite_right_open_interval(0.0)),
+            Some(Interval::right_infinite_right_open_interval(0.5))
+        )));
+
+        second.add_right_infinite_interval(0, 4.0, None);
+        let mismatched: DiagramMatching<f64> =
+            compute_annotated_bottleneck_distance(&first, &second, 0);
+
+        assert_eq!(mismatched.get_distance(), f64::INFINITY);
+        assert!(mismatched.get_matched_pairs().contains(&(
+            None,
+            Some(Interval::right_infinite_right_open_interval(4.0))
+        )));
+    }
+}
@@ -1,11 +1,15 @@
 #![allow(dead_code)]
 pub mod intervals {
     use core::cmp::Ordering::*;
+    use std::cmp::Ordering;
     use std::{
         fmt::{Debug, Display},
         ops::Sub,
     };
 
+    #[cfg(feature = "serde")]
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
     const UID: i64 = -3434702188482864510;
 
     #[derive(Clone, PartialEq)]
@@ -33,8 +37,8 @@ pub mod intervals {
         ) -> Self {
             Interval {
                 serial_version_uid: UID,
-                start: start,
-                end: end,
+                start,
+                end,
                 is_left_closed,
                 is_right_closed,
                 is_left_infinite,
@@ -90,10 +94,26 @@ pub mod intervals {
             Interval::new(None::<T>, Some(end), false, false, true, false)
         }
 
-        pub fn is_infinite(self) -> bool {
+        pub fn is_infinite(&self) -> bool {
             self.is_left_infinite || self.is_right_infinite
         }
 
+        pub fn is_left_infinite(&self) -> bool {
+            return self.is_left_infinite;
+        }
+
+        pub fn is_right_infinite(&self) -> bool {
+            return self.is_right_infinite;
+        }
+
+        pub fn is_left_closed(&self) -> bool {
+            return self.is_left_closed;
+        }
+
+        pub fn is_right_closed(&self) -> bool {
+            return self.is_right_closed;
+        }
+
         pub fn get_start(self) -> Option<T> {
             return self.start;
         }
@@ -104,17 +124,21 @@ pub mod intervals {
 
         pub fn contains_point(&self, point: T) -> bool {
             if !self.is_left_infinite {
-                if self.is_left_closed && &point < self.start.as_ref().unwrap_or(&point) {
-                    return false;
-                } else if !self.is_left_closed && &point <= self.start.as_ref().unwrap_or(&point) {
+                let start: &T = self.start.as_ref().unwrap_or(&point);
+
+                if (self.is_left_closed && &point < start)
+                    || (!self.is_left_closed && &point <= start)
+                {
                     return false;
                 }
             }
 
             if !self.is_right_infinite {
-                if self.is_right_closed && &point > self.end.as_ref().unwrap_or(&point) {
-                    return false;
-                } else if !self.is_right_closed && &point >= self.end.as_ref().unwrap_or(&point) {
+                let end: &T = self.end.as_ref().unwrap_or(&point);
+
+                if (self.is_right_closed && &point > end)
+                    || (!self.is_right_closed && &point >= end)
+                {
                     return false;
                 }
             }
@@ -123,12 +147,222 @@ pub mod intervals {
         }
     }
 
+    impl<T> Interval<T>
+    where
+        T: PartialEq + PartialOrd + Clone + Debug,
+    {
+        // Total order on left ends: -infinity first, then by value, a closed end coming
+        // before an open one at the same value. Incomparable values are treated as equal.
+        pub fn compare_starts(&self, other: &Self) -> Ordering {
+            let value_order: Ordering = match (self.is_left_infinite, other.is_left_infinite) {
+                (true, true) => Equal,
+                (true, false) => Less,
+                (false, true) => Greater,
+                (false, false) => self.start.partial_cmp(&other.start).unwrap_or(Equal),
+            };
+
+            return value_order.then(other.is_left_closed.cmp(&self.is_left_closed));
+        }
+
+        // Total order on right ends: infinity last, then by value, an open end coming
+        // before a closed one at the same value
+        pub fn compare_ends(&self, other: &Self) -> Ordering {
+            let value_order: Ordering = match (self.is_right_infinite, other.is_right_infinite) {
+                (true, true) => Equal,
+                (true, false) => Greater,
+                (false, true) => Less,
+                (false, false) => self.end.partial_cmp(&other.end).unwrap_or(Equal),
+            };
+
+            return value_order.then(self.is_right_closed.cmp(&other.is_right_closed));
+        }
+
+        // By left end and then by right end
+        pub fn compare_lexicographic(&self, other: &Self) -> Ordering {
+            return self.compare_starts(other).then(self.compare_ends(other));
+        }
+
+        // By birth, intervals born together putting the longer one first
+        pub fn compare_by_birth(&self, other: &Self) -> Ordering {
+            return self.compare_starts(other).then(other.compare_ends(self));
+        }
+
+        // By death, intervals dying together putting the longer one first
+        pub fn compare_by_death(&self, other: &Self) -> Ordering {
+            return self.compare_ends(other).then(self.compare_starts(other));
+        }
+    }
+
+    impl<T> Interval<T>
+    where
+        T: PartialEq + PartialOrd + Clone + Debug,
+    {
+        // Whether no point lies in the interval, e.g. [2, 1] or [1, 1)
+        pub fn is_empty(&self) -> bool {
+            if self.is_infinite() {
+                return false;
+            }
+
+            return match self.start.partial_cmp(&self.end) {
+                Some(Less) => false,
+                Some(Equal) => !(self.is_left_closed && self.is_right_closed),
+                _ => true,
+            };
+        }
+
+        // The later of the two starts and the earlier of the two ends, None when they do
+        // not meet
+        pub fn intersection(&self, other: &Self) -> Option<Self> {
+            let start_source: &Self = if self.compare_starts(other) == Less {
+                other
+            } else {
+                self
+            };
+            let end_source: &Self = if self.compare_ends(other) == Greater {
+                other
+            } else {
+                self
+            };
+            let intersection: Self = Self::from_ends(start_source, end_source);
+
+            if intersection.is_empty() {
+                return None;
+            }
+
+            return Some(intersection);
+        }
+
+        // The smallest interval containing both
+        pub fn hull(&self, other: &Self) -> Self {
+            let start_source: &Self = if self.compare_starts(other) == Greater {
+                other
+            } else {
+                self
+            };
+            let end_source: &Self = if self.compare_ends(other) == Less {
+                other
+            } else {
+                self
+            };
+
+            return Self::from_ends(start_source, end_source);
+        }
+
+        // The union as an interval, None when it would have a gap
+        pub fn union(&self, other: &Self) -> Option<Self> {
+            if self.is_empty() {
+                return Some(other.clone());
+            }
+
+            if other.is_empty() {
+                return Some(self.clone());
+            }
+
+            if self.overlaps(other) || self.is_adjacent_to(other) {
+                return Some(self.hull(other));
+            }
+
+            return None;
+        }
+
+        pub fn overlaps(&self, other: &Self) -> bool {
+            return self.intersection(other).is_some();
+        }
+
+        pub fn is_subset_of(&self, other: &Self) -> bool {
+            return self.is_empty()
+                || (other.compare_starts(self) != Greater && self.compare_ends(other) != Greater);
+        }
+
+        // Whether the intervals are disjoint but leave no gap, like [0, 1) and [1, 2]
+        pub fn is_adjacent_to(&self, other: &Self) -> bool {
+            let touches = |left: &Self, right: &Self| -> bool {
+                !left.is_right_infinite
+                    && !right.is_left_infinite
+                    && left.end == right.start
+                    && left.is_right_closed != right.is_left_closed
+            };
+
+            return !self.is_empty()
+                && !other.is_empty()
+                && (touches(self, other) || touches(other, self));
+        }
+
+        fn from_ends(start_source: &Self, end_source: &Self) -> Self {
+            return Self::new(
+                start_source.start.clone(),
+                end_source.end.clone(),
+                start_source.is_left_closed,
+                end_source.is_right_closed,
+                start_source.is_left_infinite,
+                end_source.is_right_infinite,
+            );
+        }
+    }
+
+    impl<T> Interval<T>
+    where
+        T: PartialEq + PartialOrd + Sub<Output = T> + Clone + Debug,
+    {
+        // Lebesgue measure end - start, which does not depend on whether the ends are
+        // included. None for infinite intervals and zero for empty ones.
+        pub fn get_length(&self) -> Option<T> {
+            if self.is_infinite() {
+                return None;
+            }
+
+            let start: T = self.start.clone().unwrap();
+
+            if self.is_empty() {
+                return Some(start.clone() - start);
+            }
+
+            return Some(self.end.clone().unwrap() - start);
+        }
+
+        // By length, infinite intervals after all finite ones, and ties broken
+        // lexicographically. Unlike partial_cmp this is a total order.
+        pub fn compare_by_persistence(&self, other: &Self) -> Ordering {
+            let length_order: Ordering = match (self.is_infinite(), other.is_infinite()) {
+                (true, true) => Equal,
+                (true, false) => Greater,
+                (false, true) => Less,
+                (false, false) => self.partial_cmp(other).unwrap_or(Equal),
+            };
+
+            return length_order.then(self.compare_lexicographic(other));
+        }
+    }
+
+    // The orderings barcodes can be sorted by
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum IntervalOrdering {
+        Birth,
+        Death,
+        Persistence,
+        Lexicographic,
+    }
+
+    impl IntervalOrdering {
+        pub fn compare<T>(&self, a: &Interval<T>, b: &Interval<T>) -> Ordering
+        where
+            T: PartialEq + PartialOrd + Sub<Output = T> + Clone + Debug,
+        {
+            return match self {
+                IntervalOrdering::Birth => a.compare_by_birth(b),
+                IntervalOrdering::Death => a.compare_by_death(b),
+                IntervalOrdering::Persistence => a.compare_by_persistence(b),
+                IntervalOrdering::Lexicographic => a.compare_lexicographic(b),
+            };
+        }
+    }
+
     impl<T> Debug for Interval<T>
     where
         T: PartialEq + PartialOrd + Debug + Clone,
     {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            return write!(f, "{}", self.to_string());
+            return write!(f, "{}", self);
         }
     }
 
@@ -158,39 +392,122 @@ pub mod intervals {
         }
     }
 
-    impl<T> Display for Interval<T>
+    // Serialized form of an interval. The infinite ends are tagged explicitly instead of
+    // relying on the value type (formats like JSON have no infinite floats), and the serial
+    // version is kept so that incompatible encodings are rejected.
+    #[cfg(feature = "serde")]
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    enum LeftEndpoint<T> {
+        NegativeInfinity,
+        Finite(T),
+    }
+
+    #[cfg(feature = "serde")]
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    enum RightEndpoint<T> {
+        Finite(T),
+        Infinity,
+    }
+
+    #[cfg(feature = "serde")]
+    #[derive(Serialize, Deserialize)]
+    struct SerializedInterval<T> {
+        serial_version_uid: i64,
+        start: LeftEndpoint<T>,
+        end: RightEndpoint<T>,
+        is_left_closed: bool,
+        is_right_closed: bool,
+    }
+
+    #[cfg(feature = "serde")]
+    impl<T> Serialize for Interval<T>
     where
-        T: Clone + Debug,
+        T: Serialize,
     {
-        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            let left_interval_symbol: &str;
-            let left_value_str: String;
-            let right_interval_symbol: &str;
-            let right_value_str: String;
-
-            if self.is_left_closed {
-                left_interval_symbol = "[";
-            } else {
-                left_interval_symbol = "(";
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            let start: LeftEndpoint<&T> = match (self.is_left_infinite, self.start.as_ref()) {
+                (false, Some(start)) => LeftEndpoint::Finite(start),
+                _ => LeftEndpoint::NegativeInfinity,
+            };
+            let end: RightEndpoint<&T> = match (self.is_right_infinite, self.end.as_ref()) {
+                (false, Some(end)) => RightEndpoint::Finite(end),
+                _ => RightEndpoint::Infinity,
+            };
+
+            return SerializedInterval {
+                serial_version_uid: self.serial_version_uid,
+                start,
+                end,
+                is_left_closed: self.is_left_closed,
+                is_right_closed: self.is_right_closed,
             }
+            .serialize(serializer);
+        }
+    }
 
-            if self.is_left_infinite {
-                left_value_str = String::from("-infinity");
-            } else {
-                left_value_str = format!("{:?}", self.start.clone().unwrap());
+    #[cfg(feature = "serde")]
+    impl<'de, T> Deserialize<'de> for Interval<T>
+    where
+        T: Deserialize<'de>,
+    {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let serialized: SerializedInterval<T> = SerializedInterval::deserialize(deserializer)?;
+
+            if serialized.serial_version_uid != UID {
+                return Err(serde::de::Error::custom(format!(
+                    "Unsupported interval serial version {}",
+                    serialized.serial_version_uid
+                )));
             }
 
-            if self.is_right_closed {
-                right_interval_symbol = "]";
+            let (start, is_left_infinite): (Option<T>, bool) = match serialized.start {
+                LeftEndpoint::NegativeInfinity => (None, true),
+                LeftEndpoint::Finite(start) => (Some(start), false),
+            };
+            let (end, is_right_infinite): (Option<T>, bool) = match serialized.end {
+                RightEndpoint::Finite(end) => (Some(end), false),
+                RightEndpoint::Infinity => (None, true),
+            };
+
+            return Ok(Interval {
+                serial_version_uid: UID,
+                start,
+                end,
+                is_left_closed: serialized.is_left_closed,
+                is_right_closed: serialized.is_right_closed,
+                is_left_infinite,
+                is_right_infinite,
+            });
+        }
+    }
+
+    impl<T> Display for Interval<T>
+    where
+        T: Clone + Debug,
+    {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            let left_interval_symbol: &str = if self.is_left_closed { "[" } else { "(" };
+            let right_interval_symbol: &str = if self.is_right_closed { "]" } else { ")" };
+
+            let left_value_str: String = if self.is_left_infinite {
+                String::from("-infinity")
             } else {
-                right_interval_symbol = ")";
-            }
+                format!("{:?}", self.start.clone().unwrap())
+            };
 
-            if self.is_right_infinite {
-                right_value_str = String::from("infinity");
+            let right_value_str: String = if self.is_right_infinite {
+                String::from("infinity")
             } else {
-                right_value_str = format!("{:?}", self.end.clone().unwrap());
-            }
+                format!("{:?}", self.end.clone().unwrap())
+            };
 
             return write!(
                 f,
@@ -291,4 +608,152 @@ mod tests {
         );
         assert_eq!(finite_interval.partial_cmp(&infinite_interval), Some(Less));
     }
+
+    #[test]
+    fn test_total_orders() {
+        let mut intervals: Vec<Interval<i32>> = vec![
+            Interval::right_infinite_right_open_interval(0),
+            Interval::finite_right_open_interval(1, 4),
+            Interval::finite_closed_interval(0, 2),
+            Interval::finite_right_open_interval(0, 2),
+            Interval::left_infinite_right_open_interval(3),
+            Interval::finite_left_open_interval(0, 5),
+        ];
+
+        intervals.sort_by(|a, b| a.compare_lexicographic(b));
+        assert_eq!(
+            intervals
+                .iter()
+                .map(|i| i.to_string())
+                .collect::<Vec<String>>(),
+            vec![
+                "[-infinity, 3)",
+                "[0, 2)",
+                "[0, 2]",
+                "[0, infinity)",
+                "(0, 5]",
+                "[1, 4)"
+            ]
+        );
+
+        intervals.sort_by(|a, b| a.compare_by_birth(b));
+        assert_eq!(
+            intervals[1],
+            Interval::right_infinite_right_open_interval(0)
+        );
+        assert_eq!(intervals[3], Interval::finite_right_open_interval(0, 2));
+
+        intervals.sort_by(|a, b| a.compare_by_death(b));
+        assert_eq!(intervals[0], Interval::finite_right_open_interval(0, 2));
+        assert_eq!(intervals[2], Interval::left_infinite_right_open_interval(3));
+        assert_eq!(
+            intervals[5],
+            Interval::right_infinite_right_open_interval(0)
+        );
+
+        intervals.sort_by(|a, b| a.compare_by_persistence(b));
+        assert_eq!(intervals[0], Interval::finite_right_open_interval(0, 2));
+        assert_eq!(intervals[2], Interval::finite_right_open_interval(1, 4));
+        assert_eq!(intervals[4], Interval::left_infinite_right_open_interval(3));
+
+        let a: Interval<i32> = Interval::right_infinite_closed_interval(1);
+        let b: Interval<i32> = Interval::left_infinite_closed_interval(1);
+        assert_eq!(a.compare_by_persistence(&b), Greater);
+        assert_eq!(b.compare_by_persistence(&a), Less);
+        assert_eq!(a.compare_by_persistence(&a), Equal);
+    }
+
+    #[test]
+    fn test_intersection_and_hull() {
+        let a: Interval<i32> = Interval::finite_closed_interval(0, 4);
+        let b: Interval<i32> = Interval::finite_left_open_interval(2, 6);
+        let c: Interval<i32> = Interval::right_infinite_right_open_interval(4);
+
+        assert_eq!(
+            a.intersection(&b),
+            Some(Interval::finite_left_open_interval(2, 4))
+        );
+        assert_eq!(
+            a.intersection(&c),
+            Some(Interval::finite_closed_interval(4, 4))
+        );
+        assert_eq!(
+            b.intersection(&c),
+            Some(Interval::finite_closed_interval(4, 6))
+        );
+        assert_eq!(
+            Interval::finite_right_open_interval(0, 4).intersection(&c),
+            None
+        );
+        assert_eq!(a.hull(&c), Interval::right_infinite_right_open_interval(0));
+        assert_eq!(
+            Interval::left_infinite_open_interval(-3).hull(&b),
+            Interval::new(None, Some(6), false, true, true, false)
+        );
+        assert!(a.overlaps(&b));
+        assert!(
+            !Interval::finite_open_interval(0, 1).overlaps(&Interval::finite_open_interval(1, 2))
+        );
+    }
+
+    #[test]
+    fn test_containment_adjacency_and_union() {
+        let a: Interval<f64> = Interval::finite_right_open_interval(0.0, 1.0);
+        let b: Interval<f64> = Interval::finite_closed_interval(1.0, 2.0);
+        let c: Interval<f64> = Interval::finite_left_open_interval(1.0, 2.0);
+
+        assert!(a.is_adjacent_to(&b));
+        assert!(b.is_adjacent_to(&a));
+        assert!(!a.is_adjacent_to(&c));
+        assert_eq!(
+            a.union(&b),
+            Some(Interval::finite_closed_interval(0.0, 2.0))
+        );
+        assert_eq!(a.union(&c), None);
+
+        assert!(c.is_subset_of(&b));
+        assert!(!b.is_subset_of(&c));
+        assert!(b.is_subset_of(&Interval::right_infinite_open_interval(0.5)));
+        assert!(Interval::finite_open_interval(3.0, 3.0).is_subset_of(&a));
+    }
+
+    #[test]
+    fn test_emptiness_and_length() {
+        assert!(Interval::finite_right_open_interval(1, 1).is_empty());
+        assert!(Interval::finite_closed_interval(2, 1).is_empty());
+        assert!(!Interval::finite_closed_interval(1, 1).is_empty());
+        assert!(!Interval::left_infinite_open_interval(1).is_empty());
+
+        assert_eq!(Interval::finite_open_interval(-2, 3).get_length(), Some(5));
+        assert_eq!(Interval::finite_closed_interval(2, 1).get_length(), Some(0));
+        assert_eq!(
+            Interval::right_infinite_closed_interval(0).get_length(),
+            None
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serialized_intervals_tag_infinite_ends() {
+        let interval: Interval<f64> = Interval::right_infinite_right_open_interval(0.5);
+        let json: String = serde_json::to_string(&interval).unwrap();
+
+        assert_eq!(
+            json,
+            "{\"serial_version_uid\":-3434702188482864510,\"start\":{\"finite\":0.5},\
+             \"end\":\"infinity\",\"is_left_closed\":true,\"is_right_closed\":false}"
+        );
+        assert_eq!(
+            serde_json::from_str::<Interval<f64>>(&json).unwrap(),
+            interval
+        );
+
+        let left_infinite: Interval<i32> = Interval::left_infinite_closed_interval(-4);
+        assert_eq!(
+            serde_json::from_str::<Interval<i32>>(&serde_json::to_string(&left_infinite).unwrap())
+                .unwrap(),
+            left_infinite
+        );
+        assert!(serde_json::from_str::<Interval<i32>>(&json.replace("-3434", "-1234")).is_err());
+    }
 }
@@ -1,10 +1,14 @@
 #![allow(dead_code)]
 pub mod persistence_invariant_descriptor {
+    use std::cmp::Ordering;
     use std::collections::hash_map::Entry::{Occupied, Vacant};
     use std::collections::HashMap;
     use std::fmt::Debug;
     use std::hash::Hash;
 
+    #[cfg(feature = "serde")]
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
     #[derive(Debug, Clone)]
     pub struct PersistenceInvariantDescriptor<I, G> {
         pub intervals: HashMap<u32, Vec<I>>,
@@ -57,6 +61,22 @@ pub mod persistence_invariant_descriptor {
             Self::get_or_default(dimension, &mut self.interval_generator_pairs)
         }
 
+        // Sorts the intervals of every dimension, keeping the generators aligned with
+        // their intervals. The sort is stable.
+        pub fn sort_intervals_by<F>(&mut self, compare: F)
+        where
+            F: Fn(&I, &I) -> Ordering,
+        {
+            for (dimension, pairs) in self.interval_generator_pairs.iter_mut() {
+                pairs.sort_by(|a, b| compare(&a.0, &b.0));
+
+                self.intervals
+                    .insert(*dimension, pairs.iter().map(|p| p.0.clone()).collect());
+                self.generators
+                    .insert(*dimension, pairs.iter().map(|p| p.1.clone()).collect());
+            }
+        }
+
         fn insert_to_vec_if_exists<T, U>(k: T, v: U, map: &mut HashMap<T, Vec<U>>)
         where
             U: Clone,
@@ -83,12 +103,95 @@ pub mod persistence_invariant_descriptor {
             }
         }
     }
+
+    // Serialized form of a descriptor. The intervals and generators are copies of the
+    // pairs, so only the pairs are written and the other two maps are rebuilt from them.
+    #[cfg(feature = "serde")]
+    #[derive(Serialize, Deserialize)]
+    struct SerializedDescriptor<P> {
+        interval_generator_pairs: P,
+    }
+
+    #[cfg(feature = "serde")]
+    impl<I, G> Serialize for PersistenceInvariantDescriptor<I, G>
+    where
+        I: Serialize,
+        G: Serialize,
+    {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            return SerializedDescriptor {
+                interval_generator_pairs: &self.interval_generator_pairs,
+            }
+            .serialize(serializer);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    impl<'de, I, G> Deserialize<'de> for PersistenceInvariantDescriptor<I, G>
+    where
+        I: Clone + Deserialize<'de>,
+        G: Clone + Deserialize<'de>,
+    {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let serialized: SerializedDescriptor<HashMap<u32, Vec<(I, G)>>> =
+                SerializedDescriptor::deserialize(deserializer)?;
+            let pairs: HashMap<u32, Vec<(I, G)>> = serialized.interval_generator_pairs;
+
+            return Ok(Self {
+                intervals: pairs
+                    .iter()
+                    .map(|(k, v)| (*k, v.iter().map(|p| p.0.clone()).collect()))
+                    .collect(),
+                generators: pairs
+                    .iter()
+                    .map(|(k, v)| (*k, v.iter().map(|p| p.1.clone()).collect()))
+                    .collect(),
+                interval_generator_pairs: pairs,
+            });
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "serde")]
+    use super::persistence_invariant_descriptor::PersistenceInvariantDescriptor;
+
     #[test]
     fn test_invariant_initialization() {
         // TODO -- will implement tests when implementing generators
     }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde_writes_only_the_pairs() {
+        let mut descriptor: PersistenceInvariantDescriptor<u32, String> =
+            PersistenceInvariantDescriptor::new();
+        descriptor.add_interval(0, 4, String::from("a"));
+        descriptor.add_interval(0, 2, String::from("b"));
+        descriptor.add_interval(1, 7, String::from("c"));
+
+        let json: String = serde_json::to_string(&descriptor).unwrap();
+        let restored: PersistenceInvariantDescriptor<u32, String> =
+            serde_json::from_str(&json).unwrap();
+
+        assert_eq!(json.matches("\"a\"").count(), 1);
+        assert!(!json.contains("\"intervals\""));
+        assert!(!json.contains("\"generators\""));
+        assert_eq!(restored.clone().get_intervals_at_dimension(0), vec![4, 2]);
+        assert_eq!(
+            restored.clone().get_generators_at_dimension(1),
+            vec![String::from("c")]
+        );
+        assert_eq!(
+            restored.get_interval_generator_pairs_at_dimension(0),
+            vec![(4, String::from("a")), (2, String::from("b"))]
+        );
+    }
 }
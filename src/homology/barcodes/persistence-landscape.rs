@@ -0,0 +1,376 @@
+#![allow(dead_code)]
+pub mod persistence_landscape {
+    use std::fmt::Debug;
+
+    use crate::intervals::intervals::Interval;
+    use crate::persistence_invariant_descriptor::persistence_invariant_descriptor::PersistenceInvariantDescriptor;
+
+    // Persistence landscape of a diagram (Bubenik). Level k is the function lambda_k, the
+    // k-th largest of the tent functions min(x - b, d - x)+ of the finite intervals [b, d).
+    // Every level is stored exactly as the critical points of a piecewise linear function,
+    // sorted by x and zero outside of them.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct PersistenceLandscape {
+        levels: Vec<Vec<(f64, f64)>>,
+    }
+
+    impl PersistenceLandscape {
+        // Landscape of the finite intervals, infinite intervals are ignored
+        pub fn from_intervals<T>(intervals: &[Interval<T>]) -> Self
+        where
+            T: Clone + Debug + PartialEq + PartialOrd + Into<f64>,
+        {
+            let mut pairs: Vec<(f64, f64)> = intervals
+                .iter()
+                .filter(|i| !i.is_infinite())
+                .map(|i| {
+                    (
+                        i.clone().get_start().unwrap().into(),
+                        i.clone().get_end().unwrap().into(),
+                    )
+                })
+                .filter(|(b, d)| d > b)
+                .collect();
+
+            return Self::from_pairs(&mut pairs);
+        }
+
+        pub fn from_descriptor<T, G>(
+            descriptor: &PersistenceInvariantDescriptor<Interval<T>, G>,
+            dimension: u32,
+        ) -> Self
+        where
+            T: Clone + Debug + PartialEq + PartialOrd + Into<f64>,
+        {
+            return match descriptor.intervals.get(&dimension) {
+                Some(intervals) => Self::from_intervals(intervals),
+                None => Self::from_levels(Vec::new()),
+            };
+        }
+
+        pub fn from_levels(levels: Vec<Vec<(f64, f64)>>) -> Self {
+            Self { levels }
+        }
+
+        // Bubenik and Dlotko's algorithm. Each level is traced from left to right by jumping
+        // to the next interval dying later than the current one, and the part of the current
+        // interval hidden below that one is pushed back for the lower levels.
+        fn from_pairs(pairs: &mut Vec<(f64, f64)>) -> Self {
+            pairs.sort_by(|a, b| a.0.total_cmp(&b.0).then(b.1.total_cmp(&a.1)));
+
+            let mut levels: Vec<Vec<(f64, f64)>> = Vec::new();
+
+            while !pairs.is_empty() {
+                let (birth, mut death): (f64, f64) = pairs.remove(0);
+                let mut level: Vec<(f64, f64)> =
+                    vec![(birth, 0.0), ((birth + death) / 2.0, (death - birth) / 2.0)];
+                let mut position: usize = 0;
+
+                while let Some(offset) = pairs[position..].iter().position(|p| p.1 > death) {
+                    let index: usize = position + offset;
+                    let (next_birth, next_death): (f64, f64) = pairs.remove(index);
+                    position = index;
+
+                    if next_birth > death {
+                        level.push((death, 0.0));
+                    }
+
+                    if next_birth >= death {
+                        level.push((next_birth, 0.0));
+                    } else {
+                        level.push(((next_birth + death) / 2.0, (death - next_birth) / 2.0));
+
+                        let insertion: usize = position
+                            + pairs[position..]
+                                .iter()
+                                .take_while(|p| {
+                                    p.0 < next_birth || (p.0 == next_birth && p.1 > death)
+                                })
+                                .count();
+                        pairs.insert(insertion, (next_birth, death));
+                    }
+
+                    level.push((
+                        (next_birth + next_death) / 2.0,
+                        (next_death - next_birth) / 2.0,
+                    ));
+                    death = next_death;
+                }
+
+                level.push((death, 0.0));
+                levels.push(level);
+            }
+
+            return Self { levels };
+        }
+
+        pub fn get_num_levels(&self) -> usize {
+            return self.levels.len();
+        }
+
+        // Critical points of lambda_k, counting levels from k = 1
+        pub fn get_level(&self, k: usize) -> &[(f64, f64)] {
+            assert!(k >= 1, "Landscape levels are numbered from 1");
+
+            return self.levels.get(k - 1).map_or(&[], |level| level.as_slice());
+        }
+
+        pub fn evaluate(&self, k: usize, x: f64) -> f64 {
+            return Self::evaluate_level(self.get_level(k), x);
+        }
+
+        fn evaluate_level(level: &[(f64, f64)], x: f64) -> f64 {
+            let index: usize = level.partition_point(|p| p.0 <= x);
+
+            if index == 0 || (index == level.len() && x > level[index - 1].0) {
+                return 0.0;
+            }
+
+            if index == level.len() {
+                return level[index - 1].1;
+            }
+
+            let ((x0, y0), (x1, y1)): ((f64, f64), (f64, f64)) = (level[index - 1], level[index]);
+
+            return y0 + (y1 - y0) * (x - x0) / (x1 - x0);
+        }
+
+        // Values of the first num_levels levels at num_samples evenly spaced points of
+        // [min_x, max_x], one row per level
+        pub fn sample(
+            &self,
+            min_x: f64,
+            max_x: f64,
+            num_samples: usize,
+            num_levels: usize,
+        ) -> Vec<Vec<f64>> {
+            let step: f64 = if num_samples > 1 {
+                (max_x - min_x) / (num_samples - 1) as f64
+            } else {
+                0.0
+            };
+
+            return (1..=num_levels)
+                .map(|k| {
+                    (0..num_samples)
+                        .map(|i| self.evaluate(k, min_x + step * i as f64))
+                        .collect()
+                })
+                .collect();
+        }
+
+        // The sampled levels concatenated into a single feature vector
+        pub fn to_vector(
+            &self,
+            min_x: f64,
+            max_x: f64,
+            num_samples: usize,
+            num_levels: usize,
+        ) -> Vec<f64> {
+            return self
+                .sample(min_x, max_x, num_samples, num_levels)
+                .into_iter()
+                .flatten()
+                .collect();
+        }
+
+        // Pointwise sum, level by level
+        pub fn add(&self, other: &PersistenceLandscape) -> Self {
+            let num_levels: usize = self.levels.len().max(other.levels.len());
+
+            return Self {
+                levels: (1..=num_levels)
+                    .map(|k| Self::add_levels(self.get_level(k), other.get_level(k)))
+                    .collect(),
+            };
+        }
+
+        fn add_levels(first: &[(f64, f64)], second: &[(f64, f64)]) -> Vec<(f64, f64)> {
+            let mut xs: Vec<f64> = first.iter().chain(second.iter()).map(|p| p.0).collect();
+            xs.sort_by(f64::total_cmp);
+            xs.dedup();
+
+            return xs
+                .into_iter()
+                .map(|x| {
+                    (
+                        x,
+                        Self::evaluate_level(first, x) + Self::evaluate_level(second, x),
+                    )
+                })
+                .collect();
+        }
+
+        pub fn scale(&self, factor: f64) -> Self {
+            return Self {
+                levels: self
+                    .levels
+                    .iter()
+                    .map(|level| level.iter().map(|(x, y)| (*x, y * factor)).collect())
+                    .collect(),
+            };
+        }
+
+        // Mean landscape, the empty landscape when there is nothing to average
+        pub fn average(landscapes: &[PersistenceLandscape]) -> Self {
+            if landscapes.is_empty() {
+                return Self::from_levels(Vec::new());
+            }
+
+            return landscapes
+                .iter()
+                .fold(Self::from_levels(Vec::new()), |sum, l| sum.add(l))
+                .scale(1.0 / landscapes.len() as f64);
+        }
+
+        // (sum_k integral |lambda_k|^p)^(1/p), or the supremum norm for p = infinity. Every
+        // linear piece is integrated exactly, splitting it where it changes sign.
+        pub fn norm(&self, p: f64) -> f64 {
+            assert!(p >= 1.0, "The exponent must be at least 1");
+
+            if p.is_infinite() {
+                return self
+                    .levels
+                    .iter()
+                    .flatten()
+                    .map(|(_, y)| y.abs())
+                    .fold(0.0, f64::max);
+            }
+
+            let mut integral: f64 = 0.0;
+
+            for level in self.levels.iter() {
+                for segment in level.windows(2) {
+                    let ((x0, y0), (x1, y1)): ((f64, f64), (f64, f64)) = (segment[0], segment[1]);
+
+                    if y0 * y1 < 0.0 {
+                        let root: f64 = x0 - y0 * (x1 - x0) / (y1 - y0);
+                        integral += Self::integrate_power(root - x0, y0, p)
+                            + Self::integrate_power(x1 - root, y1, p);
+                    } else {
+                        integral += Self::integrate_linear_power(x1 - x0, y0.abs(), y1.abs(), p);
+                    }
+                }
+            }
+
+            return integral.powf(1.0 / p);
+        }
+
+        // Integral of |y|^p over a piece going linearly between 0 and y
+        fn integrate_power(width: f64, y: f64, p: f64) -> f64 {
+            return Self::integrate_linear_power(width, 0.0, y.abs(), p);
+        }
+
+        // Integral of y^p over a piece going linearly between the non-negative a and b
+        fn integrate_linear_power(width: f64, a: f64, b: f64, p: f64) -> f64 {
+            if a == b {
+                return width * a.powf(p);
+            }
+
+            return width * (b.powf(p + 1.0) - a.powf(p + 1.0)) / ((p + 1.0) * (b - a));
+        }
+
+        pub fn distance(&self, other: &PersistenceLandscape, p: f64) -> f64 {
+            return self.add(&other.scale(-1.0)).norm(p);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::persistence_landscape::PersistenceLandscape;
+    use crate::barcode_collections::barcode_collections::AnnotatedBarcodeCollection;
+    use crate::intervals::intervals::Interval;
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-12, "{} != {}", a, b);
+    }
+
+    fn landscape(pairs: &[(f64, f64)]) -> PersistenceLandscape {
+        let intervals: Vec<Interval<f64>> = pairs
+            .iter()
+            .map(|(b, d)| Interval::finite_right_open_interval(*b, *d))
+            .collect();
+
+        return PersistenceLandscape::from_intervals(&intervals);
+    }
+
+    #[test]
+    fn test_landscape_levels() {
+        let overlapping: PersistenceLandscape = landscape(&[(0.0, 4.0), (2.0, 6.0)]);
+
+        assert_eq!(overlapping.get_num_levels(), 2);
+        assert_eq!(
+            overlapping.get_level(1),
+            &[(0.0, 0.0), (2.0, 2.0), (3.0, 1.0), (4.0, 2.0), (6.0, 0.0)]
+        );
+        assert_eq!(
+            overlapping.get_level(2),
+            &[(2.0, 0.0), (3.0, 1.0), (4.0, 0.0)]
+        );
+        assert!(overlapping.get_level(3).is_empty());
+
+        let disjoint: PersistenceLandscape = landscape(&[(3.0, 5.0), (0.0, 2.0)]);
+        assert_eq!(disjoint.get_num_levels(), 1);
+        assert_eq!(
+            disjoint.get_level(1),
+            &[
+                (0.0, 0.0),
+                (1.0, 1.0),
+                (2.0, 0.0),
+                (3.0, 0.0),
+                (4.0, 1.0),
+                (5.0, 0.0)
+            ]
+        );
+
+        assert_eq!(
+            landscape(&[(1.0, 3.0), (0.0, 4.0)]).sample(0.0, 4.0, 5, 2),
+            vec![vec![0.0, 1.0, 2.0, 1.0, 0.0], vec![0.0, 0.0, 1.0, 0.0, 0.0]]
+        );
+    }
+
+    #[test]
+    fn test_landscape_from_descriptor_skips_infinite_intervals() {
+        let mut barcodes: AnnotatedBarcodeCollection<f64, ()> =
+            AnnotatedBarcodeCollection::default();
+        barcodes.add_interval(1, 0.0, 2.0, None);
+        barcodes.add_right_infinite_interval(1, 1.0, None);
+
+        let landscape: PersistenceLandscape = PersistenceLandscape::from_descriptor(
+            barcodes.get_persistence_invariant_descriptor(),
+            1,
+        );
+
+        assert_eq!(landscape.get_num_levels(), 1);
+        assert_eq!(landscape.evaluate(1, 0.5), 0.5);
+        assert_eq!(landscape.evaluate(1, 7.0), 0.0);
+        assert_eq!(
+            PersistenceLandscape::from_descriptor(
+                barcodes.get_persistence_invariant_descriptor(),
+                0
+            )
+            .get_num_levels(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_landscape_arithmetic_and_norms() {
+        let small: PersistenceLandscape = landscape(&[(0.0, 2.0)]);
+        let large: PersistenceLandscape = landscape(&[(0.0, 4.0)]);
+
+        assert_close(small.norm(1.0), 1.0);
+        assert_close(small.norm(2.0), (2.0_f64 / 3.0).sqrt());
+        assert_close(small.norm(f64::INFINITY), 1.0);
+        assert_close(large.distance(&small, 1.0), 3.0);
+        assert_close(small.distance(&large, 1.0), 3.0);
+
+        let average: PersistenceLandscape =
+            PersistenceLandscape::average(&[small.clone(), large.clone()]);
+        assert_close(average.evaluate(1, 1.0), 1.0);
+        assert_close(average.evaluate(1, 2.0), 1.0);
+        assert_close(average.evaluate(1, 3.0), 0.5);
+        assert_close(small.scale(3.0).norm(1.0), 3.0);
+    }
+}
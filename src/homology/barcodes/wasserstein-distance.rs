@@ -0,0 +1,371 @@
+#![allow(dead_code)]
+pub mod wasserstein_distance {
+    use std::fmt::{Debug, Display};
+
+    use crate::barcode_collections::barcode_collections::{
+        AnnotatedBarcodeCollection, BarcodeCollection,
+    };
+    use crate::bottleneck_distance::bottleneck_distance::{
+        DiagramMatching, DiagramPoints, MatchedPair,
+    };
+    use crate::intervals::intervals::Interval;
+
+    // Norm on the plane used to measure how far a point of a diagram is moved
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum GroundMetric {
+        LInfinity,
+        L2,
+    }
+
+    impl GroundMetric {
+        fn point_distance(&self, a: &(usize, f64, f64), b: &(usize, f64, f64)) -> f64 {
+            let (birth_difference, death_difference): (f64, f64) =
+                ((a.1 - b.1).abs(), (a.2 - b.2).abs());
+
+            return match self {
+                GroundMetric::LInfinity => birth_difference.max(death_difference),
+                GroundMetric::L2 => birth_difference.hypot(death_difference),
+            };
+        }
+
+        // Distance to the closest point of the diagonal, its orthogonal projection
+        fn diagonal_distance(&self, a: &(usize, f64, f64)) -> f64 {
+            return match self {
+                GroundMetric::LInfinity => (a.2 - a.1) / 2.0,
+                GroundMetric::L2 => (a.2 - a.1) / 2.0_f64.sqrt(),
+            };
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum WassersteinError {
+        InvalidExponent { q: f64 },
+        MismatchedInfiniteIntervals { first: usize, second: usize },
+    }
+
+    impl Display for WassersteinError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                WassersteinError::InvalidExponent { q } => {
+                    write!(f, "The exponent {} must be a finite number at least 1", q)
+                }
+                WassersteinError::MismatchedInfiniteIntervals { first, second } => {
+                    write!(
+                        f,
+                        "The infinite intervals cannot be matched ({} against {})",
+                        first, second
+                    )
+                }
+            }
+        }
+    }
+
+    impl std::error::Error for WassersteinError {}
+
+    // q-Wasserstein distance between the diagrams of the two collections in the given
+    // dimension
+    pub fn compute_wasserstein_distance<T, G>(
+        first: &BarcodeCollection<T, G>,
+        second: &BarcodeCollection<T, G>,
+        dimension: u32,
+        q: f64,
+        ground_metric: GroundMetric,
+    ) -> Result<DiagramMatching<T>, WassersteinError>
+    where
+        T: Clone + Debug + PartialEq + PartialOrd + Into<f64>,
+        G: Clone + Debug + Default,
+    {
+        return compute_wasserstein_distance_of_intervals(
+            &first.get_intervals_at_dimension(dimension),
+            &second.get_intervals_at_dimension(dimension),
+            q,
+            ground_metric,
+        );
+    }
+
+    pub fn compute_annotated_wasserstein_distance<T, G>(
+        first: &AnnotatedBarcodeCollection<T, G>,
+        second: &AnnotatedBarcodeCollection<T, G>,
+        dimension: u32,
+        q: f64,
+        ground_metric: GroundMetric,
+    ) -> Result<DiagramMatching<T>, WassersteinError>
+    where
+        T: Clone + Debug + PartialEq + PartialOrd + Into<f64>,
+        G: Clone + Debug + Default,
+    {
+        return compute_wasserstein_distance_of_intervals(
+            &first.get_intervals_at_dimension(dimension),
+            &second.get_intervals_at_dimension(dimension),
+            q,
+            ground_metric,
+        );
+    }
+
+    // (min over matchings of the sum of ||x - m(x)||^q)^(1/q), where points may be sent to
+    // their diagonal projections. Finite points are matched with an assignment problem on
+    // the diagrams augmented by each other's projections; essential classes are paired by
+    // their finite ends, which is optimal on the line, and must agree in number.
+    pub fn compute_wasserstein_distance_of_intervals<T>(
+        first: &[Interval<T>],
+        second: &[Interval<T>],
+        q: f64,
+        ground_metric: GroundMetric,
+    ) -> Result<DiagramMatching<T>, WassersteinError>
+    where
+        T: Clone + Debug + PartialOrd + Into<f64>,
+    {
+        if !(q.is_finite() && q >= 1.0) {
+            return Err(WassersteinError::InvalidExponent { q });
+        }
+
+        let first_points: DiagramPoints = DiagramPoints::new(first);
+        let second_points: DiagramPoints = DiagramPoints::new(second);
+
+        if !first_points.has_matching_essential_classes(&second_points) {
+            return Err(WassersteinError::MismatchedInfiniteIntervals {
+                first: first.iter().filter(|i| i.is_infinite()).count(),
+                second: second.iter().filter(|i| i.is_infinite()).count(),
+            });
+        }
+
+        let mut total_cost: f64 = 0.0;
+        let mut matched_pairs: Vec<MatchedPair<T>> = Vec::new();
+
+        for (a, b, cost) in first_points.match_essential_classes(&second_points) {
+            total_cost += cost.powf(q);
+            matched_pairs.push((a.map(|a| first[a].clone()), b.map(|b| second[b].clone())));
+        }
+
+        let a_points: &Vec<(usize, f64, f64)> = &first_points.finite;
+        let b_points: &Vec<(usize, f64, f64)> = &second_points.finite;
+        let (n, m): (usize, usize) = (a_points.len(), b_points.len());
+
+        // Rows are the points of the first diagram and then the projections of the second,
+        // columns the points of the second diagram and then the projections of the first
+        let mut costs: Vec<Vec<f64>> = vec![vec![f64::INFINITY; n + m]; n + m];
+
+        for (i, a) in a_points.iter().enumerate() {
+            for (j, b) in b_points.iter().enumerate() {
+                costs[i][j] = ground_metric.point_distance(a, b).powf(q);
+            }
+
+            costs[i][m + i] = ground_metric.diagonal_distance(a).powf(q);
+        }
+
+        for (j, b) in b_points.iter().enumerate() {
+            costs[n + j][j] = ground_metric.diagonal_distance(b).powf(q);
+
+            for k in 0..n {
+                costs[n + j][m + k] = 0.0;
+            }
+        }
+
+        for (row, column) in solve_assignment(&costs).into_iter().enumerate() {
+            total_cost += costs[row][column];
+
+            if row < n && column < m {
+                matched_pairs.push((
+                    Some(first[a_points[row].0].clone()),
+                    Some(second[b_points[column].0].clone()),
+                ));
+            } else if row < n {
+                matched_pairs.push((Some(first[a_points[row].0].clone()), None));
+            } else if column < m {
+                matched_pairs.push((None, Some(second[b_points[column].0].clone())));
+            }
+        }
+
+        return Ok(DiagramMatching::new(
+            total_cost.powf(1.0 / q),
+            matched_pairs,
+        ));
+    }
+
+    // Hungarian algorithm with row and column potentials (Kuhn-Munkres in the O(n^3) form
+    // of Jonker and Volgenant), returning the column assigned to every row. Forbidden
+    // assignments are infinite costs; a finite perfect assignment must exist.
+    fn solve_assignment(costs: &[Vec<f64>]) -> Vec<usize> {
+        let n: usize = costs.len();
+        // Index 0 is a sentinel, rows and columns are numbered from 1
+        let mut row_potentials: Vec<f64> = vec![0.0; n + 1];
+        let mut column_potentials: Vec<f64> = vec![0.0; n + 1];
+        let mut column_owners: Vec<usize> = vec![0; n + 1];
+        let mut previous_columns: Vec<usize> = vec![0; n + 1];
+
+        for row in 1..=n {
+            let mut slack: Vec<f64> = vec![f64::INFINITY; n + 1];
+            let mut visited: Vec<bool> = vec![false; n + 1];
+            let mut current_column: usize = 0;
+            column_owners[0] = row;
+
+            // Grow a tree of tight edges until it reaches a free column
+            loop {
+                visited[current_column] = true;
+                let current_row: usize = column_owners[current_column];
+                let mut delta: f64 = f64::INFINITY;
+                let mut next_column: usize = 0;
+
+                for column in 1..=n {
+                    if visited[column] {
+                        continue;
+                    }
+
+                    let reduced_cost: f64 = costs[current_row - 1][column - 1]
+                        - row_potentials[current_row]
+                        - column_potentials[column];
+
+                    if reduced_cost < slack[column] {
+                        slack[column] = reduced_cost;
+                        previous_columns[column] = current_column;
+                    }
+
+                    if slack[column] < delta {
+                        delta = slack[column];
+                        next_column = column;
+                    }
+                }
+
+                for column in 0..=n {
+                    if visited[column] {
+                        row_potentials[column_owners[column]] += delta;
+                        column_potentials[column] -= delta;
+                    } else {
+                        slack[column] -= delta;
+                    }
+                }
+
+                current_column = next_column;
+
+                if column_owners[current_column] == 0 {
+                    break;
+                }
+            }
+
+            // Flip the augmenting path back to the root
+            while current_column != 0 {
+                let previous_column: usize = previous_columns[current_column];
+                column_owners[current_column] = column_owners[previous_column];
+                current_column = previous_column;
+            }
+        }
+
+        let mut assignment: Vec<usize> = vec![0; n];
+        for column in 1..=n {
+            assignment[column_owners[column] - 1] = column - 1;
+        }
+
+        return assignment;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::wasserstein_distance::{
+        compute_annotated_wasserstein_distance, compute_wasserstein_distance,
+        compute_wasserstein_distance_of_intervals, GroundMetric, WassersteinError,
+    };
+    use crate::barcode_collections::barcode_collections::{
+        AnnotatedBarcodeCollection, BarcodeCollection,
+    };
+    use crate::bottleneck_distance::bottleneck_distance::DiagramMatching;
+    use crate::intervals::intervals::Interval;
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-12, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn test_wasserstein_distance_of_finite_diagrams() {
+        let mut first: BarcodeCollection<f64, ()> = BarcodeCollection::default();
+        let mut second: BarcodeCollection<f64, ()> = BarcodeCollection::default();
+        first.add_interval(1, 0.0, 2.0);
+        first.add_interval(1, 1.0, 1.2);
+        second.add_interval(1, 0.0, 2.0);
+
+        let l_infinity: f64 =
+            compute_wasserstein_distance(&first, &second, 1, 2.0, GroundMetric::LInfinity)
+                .unwrap()
+                .get_distance();
+        let l2: f64 = compute_wasserstein_distance(&first, &second, 1, 2.0, GroundMetric::L2)
+            .unwrap()
+            .get_distance();
+
+        // Only the short bar moves, to the diagonal
+        assert_close(l_infinity, 0.1);
+        assert_close(l2, 0.2 / 2.0_f64.sqrt());
+        assert_eq!(
+            compute_wasserstein_distance(&first, &first, 1, 1.0, GroundMetric::L2)
+                .unwrap()
+                .get_distance(),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_wasserstein_distance_sums_over_points() {
+        // Matching the points to each other costs 3, sending both to the diagonal 1 + 0.5
+        let first: Vec<Interval<i32>> = vec![Interval::finite_right_open_interval(0, 2)];
+        let second: Vec<Interval<i32>> = vec![
+            Interval::finite_right_open_interval(3, 4),
+            Interval::finite_right_open_interval(0, 3),
+        ];
+
+        let matching: DiagramMatching<i32> = compute_wasserstein_distance_of_intervals(
+            &first[..],
+            &second[..1],
+            1.0,
+            GroundMetric::LInfinity,
+        )
+        .unwrap();
+        assert_close(matching.get_distance(), 1.5);
+        assert_eq!(matching.get_matched_pairs().len(), 2);
+
+        // [0, 2) now moves to [0, 3) and [3, 4) to the diagonal
+        let matching_with_third_bar: DiagramMatching<i32> =
+            compute_wasserstein_distance_of_intervals(
+                &first,
+                &second,
+                1.0,
+                GroundMetric::LInfinity,
+            )
+            .unwrap();
+        assert_close(matching_with_third_bar.get_distance(), 1.5);
+        assert!(matching_with_third_bar.get_matched_pairs().contains(&(
+            Some(Interval::finite_right_open_interval(0, 2)),
+            Some(Interval::finite_right_open_interval(0, 3))
+        )));
+    }
+
+    #[test]
+    fn test_wasserstein_distance_with_infinite_intervals() {
+        let mut first: AnnotatedBarcodeCollection<f64, ()> = AnnotatedBarcodeCollection::default();
+        let mut second: AnnotatedBarcodeCollection<f64, ()> = AnnotatedBarcodeCollection::default();
+        first.add_right_infinite_interval(0, 0.0, None);
+        first.add_right_infinite_interval(0, 1.0, None);
+        second.add_right_infinite_interval(0, 3.0, None);
+        second.add_right_infinite_interval(0, 0.5, None);
+
+        assert_close(
+            compute_annotated_wasserstein_distance(&first, &second, 0, 1.0, GroundMetric::L2)
+                .unwrap()
+                .get_distance(),
+            2.5,
+        );
+
+        second.add_right_infinite_interval(0, 4.0, None);
+        assert_eq!(
+            compute_annotated_wasserstein_distance(&first, &second, 0, 1.0, GroundMetric::L2)
+                .unwrap_err(),
+            WassersteinError::MismatchedInfiniteIntervals {
+                first: 2,
+                second: 3
+            }
+        );
+        assert_eq!(
+            compute_annotated_wasserstein_distance(&first, &first, 0, 0.5, GroundMetric::L2)
+                .unwrap_err(),
+            WassersteinError::InvalidExponent { q: 0.5 }
+        );
+    }
+}
@@ -0,0 +1,73 @@
+#![allow(dead_code)]
+pub mod cell {
+    use std::cmp::Ordering;
+    use std::fmt::{Debug, Display};
+
+    // A cell of a CW complex, identified by the id handed out by the stream that created it.
+    // Unlike simplices and cubes, a cell does not know its own boundary, which is stored in
+    // the stream, as in JavaPlex's ExplicitCellStream.
+    #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct Cell {
+        id: u32,
+        dimension: u32,
+    }
+
+    impl Cell {
+        pub fn new(id: u32, dimension: u32) -> Self {
+            Self { id, dimension }
+        }
+
+        pub fn get_id(&self) -> u32 {
+            return self.id;
+        }
+
+        pub fn get_dimension(&self) -> u32 {
+            return self.dimension;
+        }
+    }
+
+    // Cells are ordered by dimension first and by id second, like simplices
+    impl Ord for Cell {
+        fn cmp(&self, other: &Self) -> Ordering {
+            return self
+                .dimension
+                .cmp(&other.dimension)
+                .then_with(|| self.id.cmp(&other.id));
+        }
+    }
+
+    impl PartialOrd for Cell {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Display for Cell {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            return write!(f, "{}-cell {}", self.dimension, self.id);
+        }
+    }
+
+    impl Debug for Cell {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            return write!(f, "{}", self);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::cell::Cell;
+
+    #[test]
+    fn test_cell_ordering_and_display() {
+        let mut cells: Vec<Cell> = vec![Cell::new(0, 2), Cell::new(3, 1), Cell::new(1, 1)];
+        cells.sort();
+
+        assert_eq!(
+            cells,
+            vec![Cell::new(1, 1), Cell::new(3, 1), Cell::new(0, 2)]
+        );
+        assert_eq!(Cell::new(3, 1).to_string(), String::from("1-cell 3"));
+    }
+}
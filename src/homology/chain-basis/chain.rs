@@ -0,0 +1,153 @@
+#![allow(dead_code)]
+pub mod chain {
+    use std::fmt::{Debug, Display};
+
+    use crate::fields::fields::Field;
+
+    // A sparse formal sum of basis elements (simplices, cells, ...) with coefficients in a
+    // field. Terms are kept sorted by basis element and never store a zero coefficient. The
+    // arithmetic is delegated to the field the coefficients belong to.
+    #[derive(Clone, PartialEq, Eq, Hash)]
+    pub struct Chain<B, E> {
+        terms: Vec<(B, E)>,
+    }
+
+    impl<B, E> Chain<B, E>
+    where
+        B: Clone + Debug + Ord,
+        E: Clone + Debug + PartialEq,
+    {
+        pub fn new() -> Self {
+            Self { terms: Vec::new() }
+        }
+
+        pub fn from_terms<F>(terms: Vec<(B, E)>, field: &F) -> Self
+        where
+            F: Field<Element = E>,
+        {
+            let mut chain: Chain<B, E> = Chain::new();
+
+            for (element, coefficient) in terms {
+                chain.add_term(element, coefficient, field);
+            }
+
+            return chain;
+        }
+
+        pub fn add_term<F>(&mut self, element: B, coefficient: E, field: &F)
+        where
+            F: Field<Element = E>,
+        {
+            match self.terms.binary_search_by(|(b, _)| b.cmp(&element)) {
+                Ok(position) => {
+                    self.terms[position].1 = field.add(&self.terms[position].1, &coefficient);
+
+                    if field.is_zero(&self.terms[position].1) {
+                        self.terms.remove(position);
+                    }
+                }
+                Err(position) => {
+                    if !field.is_zero(&coefficient) {
+                        self.terms.insert(position, (element, coefficient));
+                    }
+                }
+            }
+        }
+
+        // None when the element does not appear, i.e. has a zero coefficient
+        pub fn get_coefficient(&self, element: &B) -> Option<&E> {
+            return match self.terms.binary_search_by(|(b, _)| b.cmp(element)) {
+                Ok(position) => Some(&self.terms[position].1),
+                Err(_) => None,
+            };
+        }
+
+        pub fn get_support(&self) -> Vec<&B> {
+            return self.terms.iter().map(|(b, _)| b).collect();
+        }
+
+        pub fn iter(&self) -> std::slice::Iter<'_, (B, E)> {
+            return self.terms.iter();
+        }
+
+        pub fn len(&self) -> usize {
+            return self.terms.len();
+        }
+
+        pub fn is_empty(&self) -> bool {
+            return self.terms.is_empty();
+        }
+    }
+
+    impl<B, E> Default for Chain<B, E>
+    where
+        B: Clone + Debug + Ord,
+        E: Clone + Debug + PartialEq,
+    {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl<B, E> Display for Chain<B, E>
+    where
+        B: Debug,
+        E: Debug,
+    {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            if self.terms.is_empty() {
+                return write!(f, "0");
+            }
+
+            let term_strings: Vec<String> = self
+                .terms
+                .iter()
+                .map(|(b, c)| format!("{:?}{:?}", c, b))
+                .collect();
+
+            return write!(f, "{}", term_strings.join(" + "));
+        }
+    }
+
+    impl<B, E> Debug for Chain<B, E>
+    where
+        B: Debug,
+        E: Debug,
+    {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            return write!(f, "{}", self);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::chain::Chain;
+    use crate::fields::fields::{Field, ModularIntField};
+    use crate::simplex::simplex::Simplex;
+
+    #[test]
+    fn test_chain_terms() {
+        let field: ModularIntField = ModularIntField::new(3);
+        let mut chain: Chain<Simplex, u32> = Chain::from_terms(
+            vec![
+                (Simplex::new(&[1, 2]), 1),
+                (Simplex::new(&[0, 1]), 2),
+                (Simplex::new(&[1, 2]), 1),
+                (Simplex::new(&[0, 2]), 1),
+                (Simplex::new(&[0, 2]), 2),
+            ],
+            &field,
+        );
+
+        assert_eq!(chain.len(), 2);
+        assert_eq!(chain.get_coefficient(&Simplex::new(&[1, 2])), Some(&2));
+        assert_eq!(chain.get_coefficient(&Simplex::new(&[0, 2])), None);
+        assert_eq!(chain.to_string(), String::from("2[0,1] + 2[1,2]"));
+
+        chain.add_term(Simplex::new(&[0, 1]), field.value_of(-2), &field);
+
+        assert_eq!(chain.get_support(), vec![&Simplex::new(&[1, 2])]);
+        assert_eq!(Chain::<Simplex, u32>::new().to_string(), String::from("0"));
+    }
+}
@@ -0,0 +1,205 @@
+#![allow(dead_code)]
+pub mod cube {
+    use std::cmp::Ordering;
+    use std::fmt::{Debug, Display};
+
+    // An elementary cube of a cubical grid, the product of unit intervals and points along
+    // each axis. Cubes are stored in doubled coordinates: an even coordinate 2i is the
+    // point i, an odd coordinate 2i + 1 the interval [i, i + 1]. The dimension of a cube is
+    // the number of its odd coordinates.
+    #[derive(Clone, PartialEq, Eq, Hash)]
+    pub struct Cube {
+        coordinates: Vec<u32>,
+    }
+
+    impl Cube {
+        pub fn new(coordinates: &[u32]) -> Self {
+            assert!(
+                !coordinates.is_empty(),
+                "A cube must have at least one coordinate"
+            );
+
+            Self {
+                coordinates: coordinates.to_vec(),
+            }
+        }
+
+        // The vertex at the given point of the grid
+        pub fn vertex(point: &[u32]) -> Self {
+            return Self::new(&point.iter().map(|c| 2 * c).collect::<Vec<u32>>());
+        }
+
+        pub fn get_coordinates(&self) -> &[u32] {
+            return &self.coordinates;
+        }
+
+        pub fn get_embedding_dimension(&self) -> usize {
+            return self.coordinates.len();
+        }
+
+        pub fn get_dimension(&self) -> u32 {
+            return self.coordinates.iter().filter(|c| *c % 2 == 1).count() as u32;
+        }
+
+        // The points of the grid at the corners of the cube
+        pub fn get_vertices(&self) -> Vec<Vec<u32>> {
+            let mut vertices: Vec<Vec<u32>> = vec![Vec::new()];
+
+            for c in self.coordinates.iter() {
+                let choices: Vec<u32> = if c % 2 == 1 {
+                    vec![c / 2, c / 2 + 1]
+                } else {
+                    vec![c / 2]
+                };
+
+                vertices = vertices
+                    .into_iter()
+                    .flat_map(|v| {
+                        choices.iter().map(move |choice| {
+                            let mut vertex: Vec<u32> = v.clone();
+                            vertex.push(*choice);
+                            vertex
+                        })
+                    })
+                    .collect();
+            }
+
+            return vertices;
+        }
+
+        // Faces paired with their boundary coefficients. With a_1 < ... < a_k the axes along
+        // which the cube is an interval, the boundary is the sum over j of (-1)^(j - 1) times
+        // the upper minus the lower face along a_j.
+        pub fn get_boundary(&self) -> Vec<(Cube, i64)> {
+            let mut boundary: Vec<(Cube, i64)> = Vec::new();
+            let mut sign: i64 = 1;
+
+            for (axis, c) in self.coordinates.iter().enumerate() {
+                if c % 2 == 0 {
+                    continue;
+                }
+
+                let mut lower: Vec<u32> = self.coordinates.clone();
+                lower[axis] = c - 1;
+                let mut upper: Vec<u32> = self.coordinates.clone();
+                upper[axis] = c + 1;
+
+                boundary.push((Cube { coordinates: lower }, -sign));
+                boundary.push((Cube { coordinates: upper }, sign));
+                sign = -sign;
+            }
+
+            return boundary;
+        }
+
+        pub fn get_boundary_array(&self) -> Vec<Cube> {
+            return self
+                .get_boundary()
+                .into_iter()
+                .map(|(face, _)| face)
+                .collect();
+        }
+    }
+
+    // Cubes are ordered by dimension first and by coordinates second, like simplices
+    impl Ord for Cube {
+        fn cmp(&self, other: &Self) -> Ordering {
+            return self
+                .get_dimension()
+                .cmp(&other.get_dimension())
+                .then_with(|| self.coordinates.cmp(&other.coordinates));
+        }
+    }
+
+    impl PartialOrd for Cube {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    // Written as a product in grid coordinates, e.g. [0,1]x[2]
+    impl Display for Cube {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            let factors: Vec<String> = self
+                .coordinates
+                .iter()
+                .map(|c| {
+                    if c % 2 == 1 {
+                        format!("[{},{}]", c / 2, c / 2 + 1)
+                    } else {
+                        format!("[{}]", c / 2)
+                    }
+                })
+                .collect();
+
+            return write!(f, "{}", factors.join("x"));
+        }
+    }
+
+    impl Debug for Cube {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            return write!(f, "{}", self);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::cube::Cube;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_cube_dimension_and_vertices() {
+        let square: Cube = Cube::new(&[1, 4, 3]);
+
+        assert_eq!(square.get_dimension(), 2);
+        assert_eq!(square.get_embedding_dimension(), 3);
+        assert_eq!(square.to_string(), String::from("[0,1]x[2]x[1,2]"));
+        assert_eq!(
+            square.get_vertices(),
+            vec![vec![0, 2, 1], vec![0, 2, 2], vec![1, 2, 1], vec![1, 2, 2]]
+        );
+        assert_eq!(Cube::vertex(&[1, 2]), Cube::new(&[2, 4]));
+    }
+
+    #[test]
+    fn test_boundary_with_orientation() {
+        let square: Cube = Cube::new(&[1, 1]);
+
+        assert_eq!(
+            square.get_boundary(),
+            vec![
+                (Cube::new(&[0, 1]), -1),
+                (Cube::new(&[2, 1]), 1),
+                (Cube::new(&[1, 0]), 1),
+                (Cube::new(&[1, 2]), -1),
+            ]
+        );
+        assert!(Cube::vertex(&[3]).get_boundary().is_empty());
+    }
+
+    #[test]
+    fn test_boundary_of_boundary_vanishes() {
+        let cube: Cube = Cube::new(&[1, 3, 5]);
+        let mut second_boundary: HashMap<Cube, i64> = HashMap::new();
+
+        for (face, coefficient) in cube.get_boundary() {
+            for (edge, edge_coefficient) in face.get_boundary() {
+                *second_boundary.entry(edge).or_insert(0) += coefficient * edge_coefficient;
+            }
+        }
+
+        assert!(second_boundary.values().all(|c| *c == 0));
+    }
+
+    #[test]
+    fn test_cube_ordering() {
+        let mut cubes: Vec<Cube> = vec![Cube::new(&[1, 1]), Cube::new(&[2, 1]), Cube::new(&[2, 0])];
+        cubes.sort();
+
+        assert_eq!(
+            cubes,
+            vec![Cube::new(&[2, 0]), Cube::new(&[2, 1]), Cube::new(&[1, 1])]
+        );
+    }
+}
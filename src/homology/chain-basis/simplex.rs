@@ -0,0 +1,248 @@
+#![allow(dead_code)]
+pub mod simplex {
+    use std::cmp::Ordering;
+    use std::fmt::{Debug, Display};
+
+    // A simplex is stored as its sorted, duplicate-free vertex list. The orientation
+    // of the simplex is the one induced by the increasing vertex order, exactly as
+    // in the JavaPlex implementation.
+    #[derive(Clone, PartialEq, Eq, Hash)]
+    pub struct Simplex {
+        vertices: Vec<u32>,
+    }
+
+    impl Simplex {
+        pub fn new(vertices: &[u32]) -> Self {
+            assert!(
+                !vertices.is_empty(),
+                "A simplex must contain at least one vertex"
+            );
+
+            let mut sorted_vertices: Vec<u32> = vertices.to_vec();
+            sorted_vertices.sort_unstable();
+            sorted_vertices.dedup();
+
+            Self {
+                vertices: sorted_vertices,
+            }
+        }
+
+        pub fn vertex(vertex: u32) -> Self {
+            Self {
+                vertices: vec![vertex],
+            }
+        }
+
+        pub fn get_vertices(&self) -> &[u32] {
+            return &self.vertices;
+        }
+
+        pub fn get_dimension(&self) -> u32 {
+            return (self.vertices.len() - 1) as u32;
+        }
+
+        pub fn contains_vertex(&self, vertex: u32) -> bool {
+            return self.vertices.binary_search(&vertex).is_ok();
+        }
+
+        pub fn is_face_of(&self, other: &Simplex) -> bool {
+            return self.vertices.iter().all(|v| other.contains_vertex(*v));
+        }
+
+        // The i-th face is obtained by deleting the i-th vertex. Vertices have no faces.
+        pub fn get_boundary_array(&self) -> Vec<Simplex> {
+            if self.vertices.len() == 1 {
+                return Vec::new();
+            }
+
+            return (0..self.vertices.len())
+                .map(|i| {
+                    let mut face_vertices: Vec<u32> = self.vertices.clone();
+                    face_vertices.remove(i);
+                    Simplex {
+                        vertices: face_vertices,
+                    }
+                })
+                .collect();
+        }
+
+        pub fn get_boundary_coefficients(&self) -> Vec<i64> {
+            if self.vertices.len() == 1 {
+                return Vec::new();
+            }
+
+            return (0..self.vertices.len())
+                .map(Self::orientation_sign)
+                .collect();
+        }
+
+        pub fn get_boundary(&self) -> Vec<(Simplex, i64)> {
+            return self
+                .get_boundary_array()
+                .into_iter()
+                .zip(self.get_boundary_coefficients())
+                .collect();
+        }
+
+        // All simplices of one dimension higher that can be formed by adding a vertex
+        // from the given vertex set.
+        pub fn get_cofaces(&self, vertex_set: &[u32]) -> Vec<Simplex> {
+            return self
+                .get_coboundary(vertex_set)
+                .into_iter()
+                .map(|(coface, _)| coface)
+                .collect();
+        }
+
+        // Cofaces paired with the coefficient of this simplex in the boundary of the coface
+        pub fn get_coboundary(&self, vertex_set: &[u32]) -> Vec<(Simplex, i64)> {
+            let mut candidate_vertices: Vec<u32> = vertex_set.to_vec();
+            candidate_vertices.sort_unstable();
+            candidate_vertices.dedup();
+
+            return candidate_vertices
+                .into_iter()
+                .filter(|v| !self.contains_vertex(*v))
+                .map(|v| {
+                    let position: usize = self.vertices.partition_point(|u| *u < v);
+                    let mut coface_vertices: Vec<u32> = self.vertices.clone();
+                    coface_vertices.insert(position, v);
+
+                    (
+                        Simplex {
+                            vertices: coface_vertices,
+                        },
+                        Self::orientation_sign(position),
+                    )
+                })
+                .collect();
+        }
+
+        fn orientation_sign(position: usize) -> i64 {
+            if position.is_multiple_of(2) {
+                1
+            } else {
+                -1
+            }
+        }
+    }
+
+    // Simplices are ordered by dimension first and lexicographically by vertices second,
+    // which mirrors the SimplexComparator used throughout JavaPlex.
+    impl Ord for Simplex {
+        fn cmp(&self, other: &Self) -> Ordering {
+            return self
+                .vertices
+                .len()
+                .cmp(&other.vertices.len())
+                .then_with(|| self.vertices.cmp(&other.vertices));
+        }
+    }
+
+    impl PartialOrd for Simplex {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Display for Simplex {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            let vertex_strings: Vec<String> = self.vertices.iter().map(|v| v.to_string()).collect();
+
+            return write!(f, "[{}]", vertex_strings.join(","));
+        }
+    }
+
+    impl Debug for Simplex {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            return write!(f, "{}", self);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::simplex::Simplex;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_simplex_normalization() {
+        let simplex: Simplex = Simplex::new(&[3, 1, 2, 1]);
+
+        assert_eq!(simplex.get_vertices(), &[1, 2, 3]);
+        assert_eq!(simplex.get_dimension(), 2);
+        assert_eq!(simplex, Simplex::new(&[1, 2, 3]));
+        assert_eq!(simplex.to_string(), String::from("[1,2,3]"));
+    }
+
+    #[test]
+    fn test_boundary_with_orientation() {
+        let triangle: Simplex = Simplex::new(&[0, 1, 2]);
+
+        assert_eq!(
+            triangle.get_boundary(),
+            vec![
+                (Simplex::new(&[1, 2]), 1),
+                (Simplex::new(&[0, 2]), -1),
+                (Simplex::new(&[0, 1]), 1),
+            ]
+        );
+        assert!(Simplex::vertex(4).get_boundary().is_empty());
+    }
+
+    #[test]
+    fn test_boundary_of_boundary_vanishes() {
+        let tetrahedron: Simplex = Simplex::new(&[0, 1, 2, 3]);
+        let mut second_boundary: HashMap<Simplex, i64> = HashMap::new();
+
+        for (face, coefficient) in tetrahedron.get_boundary() {
+            for (edge, edge_coefficient) in face.get_boundary() {
+                *second_boundary.entry(edge).or_insert(0) += coefficient * edge_coefficient;
+            }
+        }
+
+        assert!(second_boundary.values().all(|c| *c == 0));
+    }
+
+    #[test]
+    fn test_cofaces_within_vertex_set() {
+        let edge: Simplex = Simplex::new(&[1, 3]);
+
+        assert_eq!(
+            edge.get_coboundary(&[0, 1, 2, 3, 4]),
+            vec![
+                (Simplex::new(&[0, 1, 3]), 1),
+                (Simplex::new(&[1, 2, 3]), -1),
+                (Simplex::new(&[1, 3, 4]), 1),
+            ]
+        );
+
+        // The coboundary coefficients agree with the boundary coefficients of each coface
+        for (coface, coefficient) in edge.get_coboundary(&[0, 2, 4]) {
+            assert!(coface.get_boundary().contains(&(edge.clone(), coefficient)));
+        }
+    }
+
+    #[test]
+    fn test_simplex_ordering() {
+        let mut simplices: Vec<Simplex> = vec![
+            Simplex::new(&[0, 1, 2]),
+            Simplex::new(&[1, 2]),
+            Simplex::vertex(5),
+            Simplex::new(&[0, 2]),
+            Simplex::vertex(0),
+        ];
+        simplices.sort();
+
+        assert_eq!(
+            simplices,
+            vec![
+                Simplex::vertex(0),
+                Simplex::vertex(5),
+                Simplex::new(&[0, 2]),
+                Simplex::new(&[1, 2]),
+                Simplex::new(&[0, 1, 2]),
+            ]
+        );
+    }
+}
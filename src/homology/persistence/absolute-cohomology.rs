@@ -0,0 +1,435 @@
+#![allow(dead_code)]
+pub mod absolute_cohomology {
+    use std::collections::HashMap;
+    use std::fmt::Debug;
+    use std::hash::Hash;
+
+    use crate::barcode_collections::barcode_collections::{
+        AnnotatedBarcodeCollection, BarcodeCollection,
+    };
+    use crate::chain::chain::Chain;
+    use crate::fields::fields::{Field, WorkingColumn};
+    use crate::filtered_stream::filtered_stream::FilteredStream;
+
+    // Sparse column of the coboundary matrix, sorted by reversed row index without zero
+    // entries, so that the pivot (the earliest coface) is the last entry
+    type Column<E> = Vec<(usize, E)>;
+
+    struct PersistenceInterval<T, E> {
+        dimension: u32,
+        birth: T,
+        death: Option<T>,
+        representative: Option<Column<E>>,
+    }
+
+    // Persistent cohomology over the field F, computed by reducing the coboundary matrix
+    // with the clearing optimisation as in Ripser. Dimensions are processed in increasing
+    // order, and every element that was the pivot of a column one dimension lower is known
+    // to reduce to zero, so its column is skipped. The barcodes are the same as the ones of
+    // AbsoluteHomology, but far fewer columns are reduced on Rips filtrations, where most
+    // simplices of high dimension are negative. Intervals are computed for the dimensions
+    // 0, ..., max_dimension - 1.
+    pub struct AbsoluteCohomology<F> {
+        field: F,
+        max_dimension: u32,
+    }
+
+    impl<F> AbsoluteCohomology<F>
+    where
+        F: Field,
+    {
+        pub fn new(field: F, max_dimension: u32) -> Self {
+            Self {
+                field,
+                max_dimension,
+            }
+        }
+
+        pub fn compute_intervals<B, T, G, S>(&self, stream: &S) -> BarcodeCollection<T, G>
+        where
+            B: Clone + Debug + Eq + Hash,
+            T: Clone + Debug + PartialEq + PartialOrd,
+            G: Clone + Debug + Default,
+            S: FilteredStream<B, T>,
+        {
+            let (_, persistence_intervals) = self.reduce(stream, false);
+            let mut result: AnnotatedBarcodeCollection<T, G> =
+                AnnotatedBarcodeCollection::default();
+
+            for interval in persistence_intervals {
+                match interval.death {
+                    Some(death) => {
+                        result.add_interval(interval.dimension, interval.birth, death, None::<G>)
+                    }
+                    None => result.add_right_infinite_interval(
+                        interval.dimension,
+                        interval.birth,
+                        None::<G>,
+                    ),
+                }
+            }
+
+            return BarcodeCollection::new(result);
+        }
+
+        // Same as compute_intervals, but every interval is annotated with a representative
+        // cocycle, the accumulated column operations of the column giving birth to the
+        // class. A finite interval's cocycle is a cocycle of every complex before its death.
+        pub fn compute_annotated_intervals<B, T, S>(
+            &self,
+            stream: &S,
+        ) -> AnnotatedBarcodeCollection<T, Chain<B, F::Element>>
+        where
+            B: Clone + Debug + Eq + Hash + Ord,
+            T: Clone + Debug + PartialEq + PartialOrd,
+            S: FilteredStream<B, T>,
+        {
+            let (elements, persistence_intervals) = self.reduce(stream, true);
+            let mut result: AnnotatedBarcodeCollection<T, Chain<B, F::Element>> =
+                AnnotatedBarcodeCollection::default();
+
+            for interval in persistence_intervals {
+                let generator: Chain<B, F::Element> = Chain::from_terms(
+                    interval
+                        .representative
+                        .unwrap()
+                        .into_iter()
+                        .map(|(i, c)| (elements[elements.len() - 1 - i].clone(), c))
+                        .collect(),
+                    &self.field,
+                );
+
+                match interval.death {
+                    Some(death) => result.add_interval(
+                        interval.dimension,
+                        interval.birth,
+                        death,
+                        Some(generator),
+                    ),
+                    None => result.add_right_infinite_interval(
+                        interval.dimension,
+                        interval.birth,
+                        Some(generator),
+                    ),
+                }
+            }
+
+            return result;
+        }
+
+        fn reduce<'a, B, T, S>(
+            &self,
+            stream: &'a S,
+            record_representatives: bool,
+        ) -> (Vec<&'a B>, Vec<PersistenceInterval<T, F::Element>>)
+        where
+            B: Clone + Debug + Eq + Hash,
+            T: Clone + Debug + PartialEq + PartialOrd,
+            S: FilteredStream<B, T>,
+        {
+            assert!(
+                stream.is_finalized(),
+                "The stream must be finalized before computing persistence"
+            );
+
+            // Elements above max_dimension can only kill classes we never report
+            let elements: Vec<&B> = stream
+                .iter()
+                .filter(|e| stream.get_dimension(e) <= self.max_dimension)
+                .collect();
+            let coboundaries: Vec<Column<F::Element>> = self.coboundary_columns(stream, &elements);
+            let reversed = |i: usize| -> usize { elements.len() - 1 - i };
+
+            let mut pivot_owners: Vec<Option<usize>> = vec![None; elements.len()];
+            let mut is_cleared: Vec<bool> = vec![false; elements.len()];
+            let mut reduced_columns: Vec<Column<F::Element>> = vec![Vec::new(); elements.len()];
+            let mut operation_columns: Vec<Column<F::Element>> = vec![Vec::new(); elements.len()];
+            let mut result: Vec<PersistenceInterval<T, F::Element>> = Vec::new();
+
+            // Columns are reduced in place in the working column of the field, which for Z/2 is
+            // bit-packed
+            let mut working_column: F::Column = F::Column::new(elements.len());
+            let mut working_operations: F::Column = F::Column::new(elements.len());
+
+            for dimension in 0..self.max_dimension {
+                // Columns are reduced in reverse filtration order
+                for j in (0..elements.len()).rev() {
+                    if stream.get_dimension(elements[j]) != dimension || is_cleared[j] {
+                        continue;
+                    }
+
+                    working_column.load(coboundaries[j].clone());
+                    working_operations.load(vec![(reversed(j), self.field.one())]);
+
+                    while let Some((low, low_coefficient)) = working_column.get_pivot(&self.field) {
+                        match pivot_owners[low] {
+                            Some(k) => {
+                                let pivot_coefficient: &F::Element =
+                                    &reduced_columns[k].last().unwrap().1;
+                                let factor: F::Element =
+                                    self.field.divide(&low_coefficient, pivot_coefficient);
+                                working_column.subtract_multiple(
+                                    &self.field,
+                                    &reduced_columns[k],
+                                    &factor,
+                                );
+
+                                if record_representatives {
+                                    working_operations.subtract_multiple(
+                                        &self.field,
+                                        &operation_columns[k],
+                                        &factor,
+                                    );
+                                }
+                            }
+                            None => break,
+                        }
+                    }
+
+                    let column: Column<F::Element> = working_column.unload(&self.field);
+                    let operations: Column<F::Element> = working_operations.unload(&self.field);
+
+                    let birth: T = stream.get_filtration_value(elements[j]).unwrap();
+
+                    match column.last() {
+                        Some((low, _)) => {
+                            let low: usize = *low;
+                            let death: T = stream
+                                .get_filtration_value(elements[reversed(low)])
+                                .unwrap();
+                            pivot_owners[low] = Some(j);
+                            is_cleared[reversed(low)] = true;
+
+                            // Zero persistence pairs are not reported, matching JavaPlex
+                            if birth != death {
+                                result.push(PersistenceInterval {
+                                    dimension,
+                                    birth,
+                                    death: Some(death),
+                                    representative: record_representatives
+                                        .then(|| operations.clone()),
+                                });
+                            }
+                        }
+                        // Not cleared and reduced to zero, so the cocycle never dies
+                        None => result.push(PersistenceInterval {
+                            dimension,
+                            birth,
+                            death: None,
+                            representative: record_representatives.then(|| operations.clone()),
+                        }),
+                    }
+
+                    reduced_columns[j] = column;
+
+                    if record_representatives {
+                        operation_columns[j] = operations;
+                    }
+                }
+            }
+
+            return (elements, result);
+        }
+
+        // Coboundary of every element, as columns in reversed row indices
+        fn coboundary_columns<B, T, S>(
+            &self,
+            stream: &S,
+            elements: &[&B],
+        ) -> Vec<Column<F::Element>>
+        where
+            B: Clone + Debug + Eq + Hash,
+            S: FilteredStream<B, T>,
+        {
+            let indices: HashMap<&B, usize> =
+                elements.iter().enumerate().map(|(i, e)| (*e, i)).collect();
+            let mut entries: Vec<HashMap<usize, F::Element>> = vec![HashMap::new(); elements.len()];
+
+            for (j, element) in elements.iter().enumerate() {
+                for (face, coefficient) in stream.get_boundary(element) {
+                    let index: usize = *indices
+                        .get(&face)
+                        .unwrap_or_else(|| panic!("The face {:?} is not in the stream", face));
+                    let entry: &mut F::Element = entries[index]
+                        .entry(elements.len() - 1 - j)
+                        .or_insert(self.field.zero());
+                    *entry = self.field.add(entry, &self.field.value_of(coefficient));
+                }
+            }
+
+            return entries
+                .into_iter()
+                .map(|column_entries| {
+                    let mut column: Column<F::Element> = column_entries
+                        .into_iter()
+                        .filter(|(_, c)| !self.field.is_zero(c))
+                        .collect();
+                    column.sort_unstable_by_key(|(i, _)| *i);
+
+                    column
+                })
+                .collect();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::absolute_cohomology::AbsoluteCohomology;
+    use crate::absolute_homology::absolute_homology::AbsoluteHomology;
+    use crate::barcode_collections::barcode_collections::{
+        AnnotatedBarcodeCollection, BarcodeCollection,
+    };
+    use crate::chain::chain::Chain;
+    use crate::fields::fields::{BooleanField, ModularIntField, RationalField};
+    use crate::filtered_stream::filtered_stream::FilteredStream;
+    use crate::intervals::intervals::Interval;
+    use crate::metric_spaces::metric_spaces::EuclideanMetricSpace;
+    use crate::simplex::simplex::Simplex;
+    use crate::vietoris_rips_stream::vietoris_rips_stream::VietorisRipsStream;
+
+    fn sorted_intervals(
+        barcodes: &BarcodeCollection<f64, ()>,
+        dimension: u32,
+    ) -> Vec<Interval<f64>> {
+        let mut intervals: Vec<Interval<f64>> = barcodes.get_intervals_at_dimension(dimension);
+        intervals.sort_by(|a, b| a.compare_lexicographic(b));
+
+        return intervals;
+    }
+
+    // Two noisy circles of different sizes, so that the Rips filtration has classes in
+    // dimensions 0, 1 and 2
+    fn two_circles() -> EuclideanMetricSpace {
+        let mut points: Vec<Vec<f64>> = Vec::new();
+
+        for (radius, count, offset) in [(1.0, 9, 0.0), (0.6, 7, 3.0)] {
+            for k in 0..count {
+                let angle: f64 = 2.0 * std::f64::consts::PI * k as f64 / count as f64;
+                let noise: f64 = 0.05 * ((k * 7) % 5) as f64;
+                points.push(vec![
+                    offset + (radius + noise) * angle.cos(),
+                    (radius + noise) * angle.sin(),
+                ]);
+            }
+        }
+
+        return EuclideanMetricSpace::new(points);
+    }
+
+    #[test]
+    fn test_same_barcodes_as_homology() {
+        let mut stream: VietorisRipsStream = VietorisRipsStream::new(&two_circles(), 2.5, 3);
+        stream.finalize_stream().unwrap();
+
+        let homology: BarcodeCollection<f64, ()> =
+            AbsoluteHomology::new(ModularIntField::new(3), 3).compute_intervals(&stream);
+        let cohomology: BarcodeCollection<f64, ()> =
+            AbsoluteCohomology::new(ModularIntField::new(3), 3).compute_intervals(&stream);
+        let rational: BarcodeCollection<f64, ()> =
+            AbsoluteCohomology::new(RationalField, 3).compute_intervals(&stream);
+
+        assert_eq!(cohomology.get_dimensions(), homology.get_dimensions());
+        for dimension in 0..3 {
+            assert_eq!(
+                sorted_intervals(&cohomology, dimension),
+                sorted_intervals(&homology, dimension)
+            );
+            assert_eq!(
+                sorted_intervals(&rational, dimension),
+                sorted_intervals(&homology, dimension)
+            );
+        }
+        assert_eq!(
+            cohomology
+                .get_intervals_at_dimension(1)
+                .into_iter()
+                .filter(|i| i.clone().get_end().unwrap() - i.clone().get_start().unwrap() > 0.3)
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_representative_cocycles() {
+        let square: EuclideanMetricSpace = EuclideanMetricSpace::new(vec![
+            vec![0.0, 0.0],
+            vec![1.0, 0.0],
+            vec![1.0, 1.0],
+            vec![0.0, 1.0],
+        ]);
+        let mut stream: VietorisRipsStream = VietorisRipsStream::new(&square, 2.0, 2);
+        stream.finalize_stream().unwrap();
+
+        let cocycles: AnnotatedBarcodeCollection<f64, Chain<Simplex, u32>> =
+            AbsoluteCohomology::new(ModularIntField::new(5), 2)
+                .compute_annotated_intervals(&stream);
+        let cycles: AnnotatedBarcodeCollection<f64, Chain<Simplex, u32>> =
+            AbsoluteHomology::new(ModularIntField::new(5), 2).compute_annotated_intervals(&stream);
+
+        let (interval, cocycle) = cocycles
+            .get_interval_generator_pairs_at_dimension(1)
+            .pop()
+            .unwrap();
+        let (_, cycle) = cycles
+            .get_interval_generator_pairs_at_dimension(1)
+            .pop()
+            .unwrap();
+
+        assert_eq!(
+            interval,
+            Interval::finite_right_open_interval(1.0, 2.0_f64.sqrt())
+        );
+
+        // The cocycle lives on edges and evaluates nontrivially on the cycle of the square
+        for (edge, coefficient) in cocycle.iter() {
+            assert_eq!(edge.get_dimension(), 1);
+            assert_ne!(*coefficient, 0);
+        }
+        let pairing: u32 = cycle
+            .iter()
+            .map(|(edge, c)| c * cocycle.get_coefficient(edge).cloned().unwrap_or(0))
+            .sum();
+        assert_ne!(pairing % 5, 0);
+
+        // The essential class is the cocycle taking the value one on every vertex
+        let essential: Vec<(Interval<f64>, Chain<Simplex, u32>)> = cocycles
+            .get_interval_generator_pairs_at_dimension(0)
+            .into_iter()
+            .filter(|(i, _)| i.is_infinite())
+            .collect();
+        assert_eq!(essential.len(), 1);
+        assert_eq!(essential[0].1.len(), 4);
+        assert!(essential[0].1.iter().all(|(_, c)| *c == 1));
+    }
+
+    #[test]
+    fn test_max_dimension_truncation() {
+        let mut stream: VietorisRipsStream = VietorisRipsStream::new(&two_circles(), 2.5, 3);
+        stream.finalize_stream().unwrap();
+
+        let barcodes: BarcodeCollection<f64, ()> =
+            AbsoluteCohomology::new(BooleanField, 1).compute_intervals(&stream);
+
+        assert_eq!(barcodes.get_dimensions(), vec![0]);
+        assert_eq!(barcodes.get_intervals_at_dimension(0).len(), 16);
+    }
+
+    #[test]
+    fn test_packed_and_sparse_columns_agree_over_z2() {
+        let mut stream: VietorisRipsStream = VietorisRipsStream::new(&two_circles(), 2.5, 3);
+        stream.finalize_stream().unwrap();
+
+        let packed: BarcodeCollection<f64, ()> =
+            AbsoluteCohomology::new(BooleanField, 3).compute_intervals(&stream);
+        let sparse: BarcodeCollection<f64, ()> =
+            AbsoluteCohomology::new(ModularIntField::new(2), 3).compute_intervals(&stream);
+
+        for dimension in 0..3 {
+            assert_eq!(
+                packed.get_intervals_at_dimension(dimension),
+                sparse.get_intervals_at_dimension(dimension)
+            );
+        }
+    }
+}
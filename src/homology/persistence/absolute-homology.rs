@@ -0,0 +1,475 @@
+#![allow(dead_code)]
+pub mod absolute_homology {
+    use std::collections::HashMap;
+    use std::fmt::Debug;
+    use std::hash::Hash;
+
+    use crate::barcode_collections::barcode_collections::{
+        AnnotatedBarcodeCollection, BarcodeCollection,
+    };
+    use crate::chain::chain::Chain;
+    use crate::fields::fields::{Field, WorkingColumn};
+    use crate::filtered_stream::filtered_stream::FilteredStream;
+
+    // Sparse column of the boundary matrix, sorted by row index without zero entries
+    type Column<E> = Vec<(usize, E)>;
+
+    struct PersistenceInterval<T, E> {
+        dimension: u32,
+        birth: T,
+        death: Option<T>,
+        representative: Option<Column<E>>,
+    }
+
+    // Standard column reduction persistence algorithm over the field F, the analogue of
+    // JavaPlex's AbsoluteHomology. Intervals are computed for the dimensions
+    // 0, ..., max_dimension - 1.
+    pub struct AbsoluteHomology<F> {
+        field: F,
+        max_dimension: u32,
+    }
+
+    impl<F> AbsoluteHomology<F>
+    where
+        F: Field,
+    {
+        pub fn new(field: F, max_dimension: u32) -> Self {
+            Self {
+                field,
+                max_dimension,
+            }
+        }
+
+        pub fn compute_intervals<B, T, G, S>(&self, stream: &S) -> BarcodeCollection<T, G>
+        where
+            B: Clone + Debug + Eq + Hash,
+            T: Clone + Debug + PartialEq + PartialOrd,
+            G: Clone + Debug + Default,
+            S: FilteredStream<B, T>,
+        {
+            let (_, persistence_intervals) = self.reduce(stream, false);
+            let mut result: AnnotatedBarcodeCollection<T, G> =
+                AnnotatedBarcodeCollection::default();
+
+            for interval in persistence_intervals {
+                match interval.death {
+                    Some(death) => {
+                        result.add_interval(interval.dimension, interval.birth, death, None::<G>)
+                    }
+                    None => result.add_right_infinite_interval(
+                        interval.dimension,
+                        interval.birth,
+                        None::<G>,
+                    ),
+                }
+            }
+
+            return BarcodeCollection::new(result);
+        }
+
+        // Same as compute_intervals, but every interval is annotated with a representative
+        // cycle. For a finite interval this is the reduced boundary column that kills the
+        // class, for an infinite one the accumulated column operations of the birth column.
+        pub fn compute_annotated_intervals<B, T, S>(
+            &self,
+            stream: &S,
+        ) -> AnnotatedBarcodeCollection<T, Chain<B, F::Element>>
+        where
+            B: Clone + Debug + Eq + Hash + Ord,
+            T: Clone + Debug + PartialEq + PartialOrd,
+            S: FilteredStream<B, T>,
+        {
+            let (elements, persistence_intervals) = self.reduce(stream, true);
+            let mut result: AnnotatedBarcodeCollection<T, Chain<B, F::Element>> =
+                AnnotatedBarcodeCollection::default();
+
+            for interval in persistence_intervals {
+                let generator: Chain<B, F::Element> = Chain::from_terms(
+                    interval
+                        .representative
+                        .unwrap()
+                        .into_iter()
+                        .map(|(i, c)| (elements[i].clone(), c))
+                        .collect(),
+                    &self.field,
+                );
+
+                match interval.death {
+                    Some(death) => result.add_interval(
+                        interval.dimension,
+                        interval.birth,
+                        death,
+                        Some(generator),
+                    ),
+                    None => result.add_right_infinite_interval(
+                        interval.dimension,
+                        interval.birth,
+                        Some(generator),
+                    ),
+                }
+            }
+
+            return result;
+        }
+
+        fn reduce<'a, B, T, S>(
+            &self,
+            stream: &'a S,
+            record_representatives: bool,
+        ) -> (Vec<&'a B>, Vec<PersistenceInterval<T, F::Element>>)
+        where
+            B: Clone + Debug + Eq + Hash,
+            T: Clone + Debug + PartialEq + PartialOrd,
+            S: FilteredStream<B, T>,
+        {
+            assert!(
+                stream.is_finalized(),
+                "The stream must be finalized before computing persistence"
+            );
+
+            // Elements above max_dimension can only kill classes we never report
+            let elements: Vec<&B> = stream
+                .iter()
+                .filter(|e| stream.get_dimension(e) <= self.max_dimension)
+                .collect();
+            let indices: HashMap<&B, usize> =
+                elements.iter().enumerate().map(|(i, e)| (*e, i)).collect();
+
+            let mut pivot_owners: Vec<Option<usize>> = vec![None; elements.len()];
+            let mut reduced_columns: Vec<Column<F::Element>> = Vec::with_capacity(elements.len());
+            let mut operation_columns: Vec<Column<F::Element>> = Vec::new();
+            let mut is_paired: Vec<bool> = vec![false; elements.len()];
+            let mut result: Vec<PersistenceInterval<T, F::Element>> = Vec::new();
+
+            // Columns are reduced in place in the working column of the field, which for Z/2 is
+            // bit-packed
+            let mut working_column: F::Column = F::Column::new(elements.len());
+            let mut working_operations: F::Column = F::Column::new(elements.len());
+
+            for (j, element) in elements.iter().enumerate() {
+                working_column.load(self.boundary_column(stream, element, &indices));
+                working_operations.load(vec![(j, self.field.one())]);
+
+                while let Some((low, low_coefficient)) = working_column.get_pivot(&self.field) {
+                    match pivot_owners[low] {
+                        Some(k) => {
+                            let pivot_coefficient: &F::Element =
+                                &reduced_columns[k].last().unwrap().1;
+                            let factor: F::Element =
+                                self.field.divide(&low_coefficient, pivot_coefficient);
+                            working_column.subtract_multiple(
+                                &self.field,
+                                &reduced_columns[k],
+                                &factor,
+                            );
+
+                            if record_representatives {
+                                working_operations.subtract_multiple(
+                                    &self.field,
+                                    &operation_columns[k],
+                                    &factor,
+                                );
+                            }
+                        }
+                        None => break,
+                    }
+                }
+
+                let column: Column<F::Element> = working_column.unload(&self.field);
+                let operations: Column<F::Element> = working_operations.unload(&self.field);
+
+                if let Some((low, _)) = column.last() {
+                    let low: usize = *low;
+                    pivot_owners[low] = Some(j);
+                    is_paired[low] = true;
+                    is_paired[j] = true;
+
+                    let dimension: u32 = stream.get_dimension(elements[low]);
+                    let birth: T = stream.get_filtration_value(elements[low]).unwrap();
+                    let death: T = stream.get_filtration_value(element).unwrap();
+
+                    // Zero persistence pairs are not reported, matching JavaPlex
+                    if dimension < self.max_dimension && birth != death {
+                        result.push(PersistenceInterval {
+                            dimension,
+                            birth,
+                            death: Some(death),
+                            representative: record_representatives.then(|| column.clone()),
+                        });
+                    }
+                }
+
+                reduced_columns.push(column);
+
+                if record_representatives {
+                    operation_columns.push(operations);
+                }
+            }
+
+            for (j, element) in elements.iter().enumerate() {
+                let dimension: u32 = stream.get_dimension(element);
+
+                if !is_paired[j] && dimension < self.max_dimension {
+                    result.push(PersistenceInterval {
+                        dimension,
+                        birth: stream.get_filtration_value(element).unwrap(),
+                        death: None,
+                        representative: record_representatives
+                            .then(|| operation_columns[j].clone()),
+                    });
+                }
+            }
+
+            return (elements, result);
+        }
+
+        fn boundary_column<B, T, S>(
+            &self,
+            stream: &S,
+            element: &B,
+            indices: &HashMap<&B, usize>,
+        ) -> Column<F::Element>
+        where
+            B: Clone + Debug + Eq + Hash,
+            S: FilteredStream<B, T>,
+        {
+            let mut entries: HashMap<usize, F::Element> = HashMap::new();
+
+            for (face, coefficient) in stream.get_boundary(element) {
+                let index: usize = *indices
+                    .get(&face)
+                    .unwrap_or_else(|| panic!("The face {:?} is not in the stream", face));
+                let entry: &mut F::Element = entries.entry(index).or_insert(self.field.zero());
+                *entry = self.field.add(entry, &self.field.value_of(coefficient));
+            }
+
+            let mut column: Column<F::Element> = entries
+                .into_iter()
+                .filter(|(_, c)| !self.field.is_zero(c))
+                .collect();
+            column.sort_unstable_by_key(|(i, _)| *i);
+
+            return column;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::absolute_homology::AbsoluteHomology;
+    use crate::barcode_collections::barcode_collections::{
+        AnnotatedBarcodeCollection, BarcodeCollection,
+    };
+    use crate::chain::chain::Chain;
+    use crate::explicit_simplex_stream::explicit_simplex_stream::ExplicitSimplexStream;
+    use crate::fields::fields::{BooleanField, ModularIntField, Rational, RationalField};
+    use crate::filtered_stream::filtered_stream::FilteredStream;
+    use crate::intervals::intervals::Interval;
+    use crate::metric_spaces::metric_spaces::EuclideanMetricSpace;
+    use crate::simplex::simplex::Simplex;
+    use crate::vietoris_rips_stream::vietoris_rips_stream::VietorisRipsStream;
+    use std::collections::HashMap;
+
+    fn filled_triangle_stream() -> ExplicitSimplexStream<u32> {
+        let mut stream: ExplicitSimplexStream<u32> = ExplicitSimplexStream::new();
+        stream.add_vertex(0, 0).unwrap();
+        stream.add_vertex(1, 0).unwrap();
+        stream.add_vertex(2, 1).unwrap();
+        stream.add_element(&[0, 1], 2).unwrap();
+        stream.add_element(&[1, 2], 2).unwrap();
+        stream.add_element(&[0, 2], 3).unwrap();
+        stream.add_element(&[0, 1, 2], 5).unwrap();
+        stream.finalize_stream().unwrap();
+
+        return stream;
+    }
+
+    #[test]
+    fn test_filled_triangle_barcodes() {
+        let stream: ExplicitSimplexStream<u32> = filled_triangle_stream();
+        let barcodes: BarcodeCollection<u32, ()> =
+            AbsoluteHomology::new(BooleanField, 3).compute_intervals(&stream);
+
+        let mut dimension_zero: Vec<Interval<u32>> = barcodes.get_intervals_at_dimension(0);
+        dimension_zero.sort_by_key(|i| i.clone().get_end());
+
+        assert_eq!(barcodes.get_dimensions(), vec![0, 1]);
+        assert_eq!(
+            dimension_zero,
+            vec![
+                Interval::right_infinite_right_open_interval(0),
+                Interval::finite_right_open_interval(0, 2),
+                Interval::finite_right_open_interval(1, 2),
+            ]
+        );
+        assert_eq!(
+            barcodes.get_intervals_at_dimension(1),
+            vec![Interval::finite_right_open_interval(3, 5)]
+        );
+        assert!(barcodes.get_intervals_at_dimension(2).is_empty());
+    }
+
+    #[test]
+    fn test_max_dimension_truncation() {
+        let stream: ExplicitSimplexStream<u32> = filled_triangle_stream();
+        let barcodes: BarcodeCollection<u32, ()> =
+            AbsoluteHomology::new(ModularIntField::new(3), 1).compute_intervals(&stream);
+
+        assert_eq!(barcodes.get_dimensions(), vec![0]);
+        assert_eq!(barcodes.get_intervals_at_dimension(0).len(), 3);
+    }
+
+    #[test]
+    fn test_hollow_tetrahedron_over_several_primes() {
+        let mut stream: ExplicitSimplexStream<u32> = ExplicitSimplexStream::new();
+        for face in [[0, 1, 2], [0, 1, 3], [0, 2, 3], [1, 2, 3]] {
+            stream.add_element(&face, 1).unwrap();
+        }
+        stream.ensure_all_faces().unwrap();
+        stream.finalize_stream().unwrap();
+
+        for prime in [2, 3, 5] {
+            let barcodes: BarcodeCollection<u32, ()> =
+                AbsoluteHomology::new(ModularIntField::new(prime), 3).compute_intervals(&stream);
+
+            assert_eq!(
+                barcodes.get_intervals_at_dimension(0),
+                vec![Interval::right_infinite_right_open_interval(1)]
+            );
+            assert!(barcodes.get_intervals_at_dimension(1).is_empty());
+            assert_eq!(
+                barcodes.get_intervals_at_dimension(2),
+                vec![Interval::right_infinite_right_open_interval(1)]
+            );
+        }
+    }
+
+    #[test]
+    fn test_representative_cycles() {
+        let stream: ExplicitSimplexStream<u32> = filled_triangle_stream();
+        let barcodes: AnnotatedBarcodeCollection<u32, Chain<Simplex, u32>> =
+            AbsoluteHomology::new(ModularIntField::new(3), 3).compute_annotated_intervals(&stream);
+
+        let pairs: Vec<(Interval<u32>, Chain<Simplex, u32>)> =
+            barcodes.get_interval_generator_pairs_at_dimension(1);
+        let (interval, cycle) = pairs.first().unwrap();
+
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(*interval, Interval::finite_right_open_interval(3, 5));
+        assert_eq!(cycle.len(), 3);
+
+        // The representative must be a cycle over Z/3
+        let mut boundary: HashMap<Simplex, i64> = HashMap::new();
+        for (edge, coefficient) in cycle.iter() {
+            for (vertex, sign) in edge.get_boundary() {
+                *boundary.entry(vertex).or_insert(0) += *coefficient as i64 * sign;
+            }
+        }
+        assert!(boundary.values().all(|c| c.rem_euclid(3) == 0));
+
+        // The essential component is represented by a single vertex
+        let essential: Vec<(Interval<u32>, Chain<Simplex, u32>)> = barcodes
+            .get_interval_generator_pairs_at_dimension(0)
+            .into_iter()
+            .filter(|(i, _)| i.is_infinite())
+            .collect();
+        assert_eq!(
+            essential[0].1.iter().collect::<Vec<_>>(),
+            vec![&(Simplex::vertex(0), 1)]
+        );
+    }
+
+    fn projective_plane_stream() -> ExplicitSimplexStream<u32> {
+        let mut stream: ExplicitSimplexStream<u32> = ExplicitSimplexStream::new();
+        for face in [
+            [0, 1, 2],
+            [0, 2, 3],
+            [0, 3, 4],
+            [0, 4, 5],
+            [0, 1, 5],
+            [1, 2, 4],
+            [2, 3, 5],
+            [1, 3, 4],
+            [1, 3, 5],
+            [2, 4, 5],
+        ] {
+            stream.add_element(&face, 0).unwrap();
+        }
+        stream.ensure_all_faces().unwrap();
+        stream.finalize_stream().unwrap();
+
+        return stream;
+    }
+
+    #[test]
+    fn test_torsion_detection_with_different_fields() {
+        let stream: ExplicitSimplexStream<u32> = projective_plane_stream();
+
+        let mod_two: BarcodeCollection<u32, ()> =
+            AbsoluteHomology::new(BooleanField, 3).compute_intervals(&stream);
+        let mod_three: BarcodeCollection<u32, ()> =
+            AbsoluteHomology::new(ModularIntField::new(3), 3).compute_intervals(&stream);
+        let rational: AnnotatedBarcodeCollection<u32, Chain<Simplex, Rational>> =
+            AbsoluteHomology::new(RationalField, 3).compute_annotated_intervals(&stream);
+
+        assert_eq!(mod_two.get_intervals_at_dimension(1).len(), 1);
+        assert_eq!(mod_two.get_intervals_at_dimension(2).len(), 1);
+        assert!(mod_three.get_intervals_at_dimension(1).is_empty());
+        assert!(mod_three.get_intervals_at_dimension(2).is_empty());
+        assert!(rational.get_intervals_at_dimension(1).is_empty());
+        assert_eq!(rational.get_intervals_at_dimension(0).len(), 1);
+    }
+
+    #[test]
+    fn test_packed_and_sparse_columns_agree_over_z2() {
+        // Two noisy circles, so that plenty of columns need several additions
+        let points: Vec<Vec<f64>> = (0..24)
+            .map(|i| {
+                let angle: f64 = i as f64 * std::f64::consts::PI / 6.0;
+                let radius: f64 = if i < 12 { 1.0 } else { 2.0 } + 0.1 * (3.0 * angle).sin();
+                vec![
+                    radius * angle.cos(),
+                    radius * angle.sin() + (i / 12) as f64 * 4.0,
+                ]
+            })
+            .collect();
+        let mut rips: VietorisRipsStream =
+            VietorisRipsStream::new(&EuclideanMetricSpace::new(points), 3.0, 3);
+        rips.finalize_stream().unwrap();
+
+        let packed: BarcodeCollection<f64, ()> =
+            AbsoluteHomology::new(BooleanField, 3).compute_intervals(&rips);
+        let sparse: BarcodeCollection<f64, ()> =
+            AbsoluteHomology::new(ModularIntField::new(2), 3).compute_intervals(&rips);
+
+        assert_eq!(packed.get_intervals_at_dimension(1).len(), 2);
+        for dimension in 0..3 {
+            assert_eq!(
+                packed.get_intervals_at_dimension(dimension),
+                sparse.get_intervals_at_dimension(dimension)
+            );
+        }
+
+        // The representatives have the same support as well
+        let stream: ExplicitSimplexStream<u32> = projective_plane_stream();
+        let packed: AnnotatedBarcodeCollection<u32, Chain<Simplex, bool>> =
+            AbsoluteHomology::new(BooleanField, 3).compute_annotated_intervals(&stream);
+        let sparse: AnnotatedBarcodeCollection<u32, Chain<Simplex, u32>> =
+            AbsoluteHomology::new(ModularIntField::new(2), 3).compute_annotated_intervals(&stream);
+
+        for dimension in 0..3 {
+            assert_eq!(
+                packed
+                    .get_generators_at_dimension(dimension)
+                    .iter()
+                    .map(|c| c.get_support())
+                    .collect::<Vec<_>>(),
+                sparse
+                    .get_generators_at_dimension(dimension)
+                    .iter()
+                    .map(|c| c.get_support())
+                    .collect::<Vec<_>>()
+            );
+        }
+    }
+}
@@ -0,0 +1,549 @@
+#![allow(dead_code)]
+pub mod relative_homology {
+    use std::cmp::Ordering::Equal;
+    use std::collections::HashMap;
+    use std::fmt::{Debug, Display};
+    use std::hash::Hash;
+
+    use crate::absolute_homology::absolute_homology::AbsoluteHomology;
+    use crate::barcode_collections::barcode_collections::{
+        AnnotatedBarcodeCollection, BarcodeCollection,
+    };
+    use crate::chain::chain::Chain;
+    use crate::fields::fields::Field;
+    use crate::filtered_stream::filtered_stream::{FilteredStream, StreamError};
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum SubcomplexError {
+        MissingElement { element: String },
+        EnteredBeforeComplex { element: String },
+        UnorderedFiltrationValue { element: String },
+        MissingFace { element: String, face: String },
+    }
+
+    impl Display for SubcomplexError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                SubcomplexError::MissingElement { element } => {
+                    write!(
+                        f,
+                        "The element {} of the subcomplex is not in the complex",
+                        element
+                    )
+                }
+                SubcomplexError::EnteredBeforeComplex { element } => {
+                    write!(
+                        f,
+                        "The element {} enters the subcomplex before the complex",
+                        element
+                    )
+                }
+                SubcomplexError::UnorderedFiltrationValue { element } => {
+                    write!(
+                        f,
+                        "The filtration value of {} is not comparable to itself",
+                        element
+                    )
+                }
+                SubcomplexError::MissingFace { element, face } => {
+                    write!(
+                        f,
+                        "The face {} of {} is not in the subcomplex",
+                        face, element
+                    )
+                }
+            }
+        }
+    }
+
+    impl std::error::Error for SubcomplexError {}
+
+    // Elements of the mapping cone K u C(L): the elements of K, the cone point, and the
+    // cone a * s on every element s of L
+    #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+    enum ConeElement<B> {
+        Apex,
+        Base(B),
+        Cone(B),
+    }
+
+    // The filtered mapping cone of the inclusion L -> K. The cone a * s enters with s in L,
+    // and the cone point before everything else.
+    struct ConeStream<'a, B, T, S> {
+        stream: &'a S,
+        elements: Vec<ConeElement<B>>,
+        filtration_values: HashMap<ConeElement<B>, T>,
+    }
+
+    impl<'a, B, T, S> ConeStream<'a, B, T, S>
+    where
+        B: Clone + Debug + Eq + Hash,
+        T: Clone + Debug + PartialOrd,
+        S: FilteredStream<B, T>,
+    {
+        fn new<L>(stream: &'a S, subcomplex: &L, apex_value: T) -> Self
+        where
+            L: FilteredStream<B, T>,
+        {
+            let mut elements: Vec<ConeElement<B>> = vec![ConeElement::Apex];
+            let mut filtration_values: HashMap<ConeElement<B>, T> = HashMap::new();
+            filtration_values.insert(ConeElement::Apex, apex_value);
+
+            for element in stream.iter() {
+                let value: T = stream.get_filtration_value(element).unwrap();
+                filtration_values.insert(ConeElement::Base(element.clone()), value);
+                elements.push(ConeElement::Base(element.clone()));
+            }
+
+            for element in subcomplex.iter() {
+                let value: T = subcomplex.get_filtration_value(element).unwrap();
+                filtration_values.insert(ConeElement::Cone(element.clone()), value);
+                elements.push(ConeElement::Cone(element.clone()));
+            }
+
+            let mut cone: ConeStream<'a, B, T, S> = Self {
+                stream,
+                elements: Vec::new(),
+                filtration_values,
+            };
+
+            // Stable, so the cone point stays ahead of the vertices entering with it. The
+            // values were checked to be ordered when validating the subcomplex.
+            elements.sort_by(|a, b| {
+                cone.filtration_values[a]
+                    .partial_cmp(&cone.filtration_values[b])
+                    .unwrap_or(Equal)
+                    .then_with(|| cone.get_dimension(a).cmp(&cone.get_dimension(b)))
+            });
+            cone.elements = elements;
+
+            return cone;
+        }
+    }
+
+    impl<B, T, S> FilteredStream<ConeElement<B>, T> for ConeStream<'_, B, T, S>
+    where
+        B: Clone + Debug + Eq + Hash,
+        T: Clone + Debug + PartialOrd,
+        S: FilteredStream<B, T>,
+    {
+        fn finalize_stream(&mut self) -> Result<(), StreamError> {
+            return Ok(());
+        }
+
+        fn is_finalized(&self) -> bool {
+            return true;
+        }
+
+        fn get_size(&self) -> usize {
+            return self.elements.len();
+        }
+
+        fn iter(&self) -> std::slice::Iter<'_, ConeElement<B>> {
+            return self.elements.iter();
+        }
+
+        fn get_filtration_value(&self, element: &ConeElement<B>) -> Option<T> {
+            return self.filtration_values.get(element).cloned();
+        }
+
+        fn get_dimension(&self, element: &ConeElement<B>) -> u32 {
+            return match element {
+                ConeElement::Apex => 0,
+                ConeElement::Base(b) => self.stream.get_dimension(b),
+                ConeElement::Cone(b) => self.stream.get_dimension(b) + 1,
+            };
+        }
+
+        // The boundary of a * s is s - a * (boundary of s), and s - a for a vertex s
+        fn get_boundary(&self, element: &ConeElement<B>) -> Vec<(ConeElement<B>, i64)> {
+            return match element {
+                ConeElement::Apex => Vec::new(),
+                ConeElement::Base(b) => self
+                    .stream
+                    .get_boundary(b)
+                    .into_iter()
+                    .map(|(face, c)| (ConeElement::Base(face), c))
+                    .collect(),
+                ConeElement::Cone(b) => {
+                    let mut boundary: Vec<(ConeElement<B>, i64)> =
+                        vec![(ConeElement::Base(b.clone()), 1)];
+
+                    if self.stream.get_dimension(b) == 0 {
+                        boundary.push((ConeElement::Apex, -1));
+                    } else {
+                        boundary.extend(
+                            self.stream
+                                .get_boundary(b)
+                                .into_iter()
+                                .map(|(face, c)| (ConeElement::Cone(face), -c)),
+                        );
+                    }
+
+                    boundary
+                }
+            };
+        }
+    }
+
+    // Persistent homology of a filtered pair (K, L), L a filtered subcomplex of K, over the
+    // field F, the analogue of JavaPlex's RelativeHomology. The relative groups H_n(K_t, L_t)
+    // are the reduced homology groups of the mapping cone K_t u C(L_t), whose persistence is
+    // computed by AbsoluteHomology; the class of the cone point is then discarded and the
+    // representatives are restricted to K. Intervals are computed for the dimensions
+    // 0, ..., max_dimension - 1.
+    pub struct RelativeHomology<F> {
+        field: F,
+        max_dimension: u32,
+    }
+
+    impl<F> RelativeHomology<F>
+    where
+        F: Field,
+    {
+        pub fn new(field: F, max_dimension: u32) -> Self {
+            Self {
+                field,
+                max_dimension,
+            }
+        }
+
+        pub fn compute_intervals<B, T, G, S, L>(
+            &self,
+            stream: &S,
+            subcomplex: &L,
+        ) -> Result<BarcodeCollection<T, G>, SubcomplexError>
+        where
+            B: Clone + Debug + Eq + Hash + Ord,
+            T: Clone + Debug + PartialEq + PartialOrd,
+            G: Clone + Debug + Default,
+            S: FilteredStream<B, T>,
+            L: FilteredStream<B, T>,
+        {
+            let annotated: AnnotatedBarcodeCollection<T, Chain<B, F::Element>> =
+                self.compute_annotated_intervals(stream, subcomplex)?;
+            let mut result: AnnotatedBarcodeCollection<T, G> =
+                AnnotatedBarcodeCollection::default();
+
+            for dimension in annotated.get_dimensions() {
+                for interval in annotated.get_intervals_at_dimension(dimension) {
+                    result.add_exact_interval(dimension, interval, None);
+                }
+            }
+
+            return Ok(BarcodeCollection::new(result));
+        }
+
+        // Every interval is annotated with a relative cycle of K, a chain whose boundary
+        // lies in L
+        pub fn compute_annotated_intervals<B, T, S, L>(
+            &self,
+            stream: &S,
+            subcomplex: &L,
+        ) -> Result<AnnotatedBarcodeCollection<T, Chain<B, F::Element>>, SubcomplexError>
+        where
+            B: Clone + Debug + Eq + Hash + Ord,
+            T: Clone + Debug + PartialEq + PartialOrd,
+            S: FilteredStream<B, T>,
+            L: FilteredStream<B, T>,
+        {
+            assert!(
+                stream.is_finalized() && subcomplex.is_finalized(),
+                "The streams must be finalized before computing persistence"
+            );
+
+            Self::validate_subcomplex(stream, subcomplex)?;
+
+            let mut result: AnnotatedBarcodeCollection<T, Chain<B, F::Element>> =
+                AnnotatedBarcodeCollection::default();
+
+            // The first element of a finalized stream enters first
+            let apex_value: T = match stream.iter().next() {
+                Some(first) => stream.get_filtration_value(first).unwrap(),
+                None => return Ok(result),
+            };
+            let cone: ConeStream<B, T, S> = ConeStream::new(stream, subcomplex, apex_value);
+            let cone_intervals: AnnotatedBarcodeCollection<T, Chain<ConeElement<B>, F::Element>> =
+                AbsoluteHomology::new(self.field.clone(), self.max_dimension)
+                    .compute_annotated_intervals(&cone);
+
+            for dimension in cone_intervals.get_dimensions() {
+                for (interval, generator) in
+                    cone_intervals.get_interval_generator_pairs_at_dimension(dimension)
+                {
+                    if generator.get_coefficient(&ConeElement::Apex).is_some()
+                        && interval.is_right_infinite()
+                    {
+                        continue;
+                    }
+
+                    let relative_cycle: Chain<B, F::Element> = Chain::from_terms(
+                        generator
+                            .iter()
+                            .filter_map(|(element, coefficient)| match element {
+                                ConeElement::Base(b) => Some((b.clone(), coefficient.clone())),
+                                _ => None,
+                            })
+                            .collect(),
+                        &self.field,
+                    );
+
+                    result.add_exact_interval(dimension, interval, Some(relative_cycle));
+                }
+            }
+
+            return Ok(result);
+        }
+
+        // Every element of L must be in K, entering no earlier than it does in K, and L must
+        // be closed under taking faces. All filtration values must be ordered so that the
+        // cone can be sorted.
+        fn validate_subcomplex<B, T, S, L>(
+            stream: &S,
+            subcomplex: &L,
+        ) -> Result<(), SubcomplexError>
+        where
+            B: Clone + Debug + Eq + Hash,
+            T: Clone + Debug + PartialOrd,
+            S: FilteredStream<B, T>,
+            L: FilteredStream<B, T>,
+        {
+            let is_unordered = |value: T| value.partial_cmp(&value).is_none();
+
+            for element in stream.iter() {
+                if is_unordered(stream.get_filtration_value(element).unwrap()) {
+                    return Err(SubcomplexError::UnorderedFiltrationValue {
+                        element: format!("{:?}", element),
+                    });
+                }
+            }
+
+            for element in subcomplex.iter() {
+                if is_unordered(subcomplex.get_filtration_value(element).unwrap()) {
+                    return Err(SubcomplexError::UnorderedFiltrationValue {
+                        element: format!("{:?}", element),
+                    });
+                }
+
+                for (face, _) in subcomplex.get_boundary(element) {
+                    if subcomplex.get_filtration_value(&face).is_none() {
+                        return Err(SubcomplexError::MissingFace {
+                            element: format!("{:?}", element),
+                            face: format!("{:?}", face),
+                        });
+                    }
+                }
+
+                match stream.get_filtration_value(element) {
+                    None => {
+                        return Err(SubcomplexError::MissingElement {
+                            element: format!("{:?}", element),
+                        });
+                    }
+                    Some(value) => {
+                        if subcomplex.get_filtration_value(element).unwrap() < value {
+                            return Err(SubcomplexError::EnteredBeforeComplex {
+                                element: format!("{:?}", element),
+                            });
+                        }
+                    }
+                }
+            }
+
+            return Ok(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::relative_homology::{RelativeHomology, SubcomplexError};
+    use crate::barcode_collections::barcode_collections::{
+        AnnotatedBarcodeCollection, BarcodeCollection,
+    };
+    use crate::chain::chain::Chain;
+    use crate::explicit_simplex_stream::explicit_simplex_stream::ExplicitSimplexStream;
+    use crate::fields::fields::{BooleanField, ModularIntField};
+    use crate::filtered_stream::filtered_stream::{FilteredStream, StreamError};
+    use crate::intervals::intervals::Interval;
+    use crate::simplex::simplex::Simplex;
+
+    fn stream_of(simplices: &[(&[u32], u32)]) -> ExplicitSimplexStream<u32> {
+        let mut stream: ExplicitSimplexStream<u32> = ExplicitSimplexStream::new();
+        for (vertices, value) in simplices.iter() {
+            stream.add_element(vertices, *value).unwrap();
+        }
+        stream.finalize_stream().unwrap();
+
+        return stream;
+    }
+
+    #[test]
+    fn test_disk_relative_to_its_boundary() {
+        let disk: ExplicitSimplexStream<u32> = stream_of(&[
+            (&[0], 0),
+            (&[1], 0),
+            (&[2], 0),
+            (&[0, 1], 0),
+            (&[1, 2], 0),
+            (&[0, 2], 0),
+            (&[0, 1, 2], 1),
+        ]);
+        let boundary: ExplicitSimplexStream<u32> = stream_of(&[
+            (&[0], 0),
+            (&[1], 0),
+            (&[2], 0),
+            (&[0, 1], 0),
+            (&[1, 2], 0),
+            (&[0, 2], 0),
+        ]);
+
+        let barcodes: AnnotatedBarcodeCollection<u32, Chain<Simplex, u32>> =
+            RelativeHomology::new(ModularIntField::new(3), 3)
+                .compute_annotated_intervals(&disk, &boundary)
+                .unwrap();
+
+        // H_2(D, S^1) is generated by the triangle once it is there
+        assert_eq!(barcodes.get_dimensions(), vec![2]);
+
+        let pairs: Vec<(Interval<u32>, Chain<Simplex, u32>)> =
+            barcodes.get_interval_generator_pairs_at_dimension(2);
+        assert_eq!(pairs[0].0, Interval::right_infinite_right_open_interval(1));
+        assert_eq!(pairs[0].1.get_support(), vec![&Simplex::new(&[0, 1, 2])]);
+    }
+
+    #[test]
+    fn test_subcomplex_entering_later() {
+        // An edge relative to one of its endpoints, which only joins L at time 2
+        let edge: ExplicitSimplexStream<u32> = stream_of(&[(&[0], 0), (&[1], 0), (&[0, 1], 1)]);
+        let endpoint: ExplicitSimplexStream<u32> = stream_of(&[(&[1], 2)]);
+
+        let barcodes: BarcodeCollection<u32, ()> = RelativeHomology::new(BooleanField, 2)
+            .compute_intervals(&edge, &endpoint)
+            .unwrap();
+
+        let mut dimension_zero: Vec<Interval<u32>> = barcodes.get_intervals_at_dimension(0);
+        dimension_zero.sort_by(|a, b| a.compare_lexicographic(b));
+
+        assert_eq!(
+            dimension_zero,
+            vec![
+                Interval::finite_right_open_interval(0, 1),
+                Interval::finite_right_open_interval(0, 2),
+            ]
+        );
+        assert!(barcodes.get_intervals_at_dimension(1).is_empty());
+
+        // With an empty subcomplex, relative homology is the absolute one
+        let empty: ExplicitSimplexStream<u32> = stream_of(&[]);
+        let absolute: BarcodeCollection<u32, ()> = RelativeHomology::new(BooleanField, 2)
+            .compute_intervals(&edge, &empty)
+            .unwrap();
+        assert_eq!(
+            absolute.get_intervals_at_dimension(0),
+            vec![
+                Interval::finite_right_open_interval(0, 1),
+                Interval::right_infinite_right_open_interval(0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_subcomplexes() {
+        let edge: ExplicitSimplexStream<u32> = stream_of(&[(&[0], 1), (&[1], 1), (&[0, 1], 1)]);
+        let homology: RelativeHomology<BooleanField> = RelativeHomology::new(BooleanField, 2);
+
+        let foreign: ExplicitSimplexStream<u32> = stream_of(&[(&[2], 1)]);
+        let early: ExplicitSimplexStream<u32> = stream_of(&[(&[0], 0)]);
+
+        assert!(matches!(
+            homology.compute_intervals::<Simplex, u32, (), _, _>(&edge, &foreign),
+            Err(SubcomplexError::MissingElement { .. })
+        ));
+        assert!(matches!(
+            homology.compute_intervals::<Simplex, u32, (), _, _>(&edge, &early),
+            Err(SubcomplexError::EnteredBeforeComplex { .. })
+        ));
+    }
+
+    // Simplices all entering at the same value, without the validation of
+    // ExplicitSimplexStream
+    struct UncheckedStream {
+        simplices: Vec<Simplex>,
+        value: f64,
+    }
+
+    impl FilteredStream<Simplex, f64> for UncheckedStream {
+        fn finalize_stream(&mut self) -> Result<(), StreamError> {
+            return Ok(());
+        }
+
+        fn is_finalized(&self) -> bool {
+            return true;
+        }
+
+        fn get_size(&self) -> usize {
+            return self.simplices.len();
+        }
+
+        fn iter(&self) -> std::slice::Iter<'_, Simplex> {
+            return self.simplices.iter();
+        }
+
+        fn get_filtration_value(&self, element: &Simplex) -> Option<f64> {
+            return self.simplices.contains(element).then_some(self.value);
+        }
+
+        fn get_dimension(&self, element: &Simplex) -> u32 {
+            return element.get_dimension();
+        }
+
+        fn get_boundary(&self, element: &Simplex) -> Vec<(Simplex, i64)> {
+            return element.get_boundary();
+        }
+    }
+
+    #[test]
+    fn test_unordered_filtration_values_are_rejected() {
+        let unordered: UncheckedStream = UncheckedStream {
+            simplices: vec![Simplex::vertex(0)],
+            value: f64::NAN,
+        };
+        let mut vertex: ExplicitSimplexStream<f64> = ExplicitSimplexStream::new();
+        vertex.add_vertex(0, 0.0).unwrap();
+        vertex.finalize_stream().unwrap();
+        let homology: RelativeHomology<BooleanField> = RelativeHomology::new(BooleanField, 1);
+
+        assert!(matches!(
+            homology.compute_intervals::<Simplex, f64, (), _, _>(&unordered, &vertex),
+            Err(SubcomplexError::UnorderedFiltrationValue { .. })
+        ));
+        assert!(matches!(
+            homology.compute_intervals::<Simplex, f64, (), _, _>(&vertex, &unordered),
+            Err(SubcomplexError::UnorderedFiltrationValue { .. })
+        ));
+    }
+
+    #[test]
+    fn test_subcomplexes_must_be_closed_under_faces() {
+        let mut edge: ExplicitSimplexStream<f64> = ExplicitSimplexStream::new();
+        edge.add_element(&[0, 1], 1.0).unwrap();
+        edge.ensure_all_faces().unwrap();
+        edge.finalize_stream().unwrap();
+        let open_edge: UncheckedStream = UncheckedStream {
+            simplices: vec![Simplex::vertex(0), Simplex::new(&[0, 1])],
+            value: 1.0,
+        };
+        let homology: RelativeHomology<BooleanField> = RelativeHomology::new(BooleanField, 2);
+
+        assert_eq!(
+            homology
+                .compute_intervals::<Simplex, f64, (), _, _>(&edge, &open_edge)
+                .err(),
+            Some(SubcomplexError::MissingFace {
+                element: format!("{:?}", Simplex::new(&[0, 1])),
+                face: format!("{:?}", Simplex::vertex(1)),
+            })
+        );
+    }
+}
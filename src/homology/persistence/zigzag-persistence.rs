@@ -0,0 +1,544 @@
+#![allow(dead_code)]
+pub mod zigzag_persistence {
+    use std::collections::{HashMap, HashSet};
+    use std::fmt::{Debug, Display};
+
+    use crate::barcode_collections::barcode_collections::{
+        AnnotatedBarcodeCollection, BarcodeCollection,
+    };
+    use crate::cell::cell::Cell;
+    use crate::explicit_cell_stream::explicit_cell_stream::ExplicitCellStream;
+    use crate::fields::fields::Field;
+    use crate::filtered_stream::filtered_stream::{FilteredStream, StreamError};
+    use crate::intervals::intervals::Interval;
+    use crate::relative_homology::relative_homology::RelativeHomology;
+    use crate::simplex::simplex::Simplex;
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum ZigzagError {
+        DecreasingTime,
+        AlreadyPresent { simplex: String },
+        NotPresent { simplex: String },
+        MissingFace { simplex: String, face: String },
+        CofacePresent { simplex: String, coface: String },
+    }
+
+    impl Display for ZigzagError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                ZigzagError::DecreasingTime => {
+                    write!(f, "Operations must be given in nondecreasing time order")
+                }
+                ZigzagError::AlreadyPresent { simplex } => {
+                    write!(f, "The simplex {} is already in the complex", simplex)
+                }
+                ZigzagError::NotPresent { simplex } => {
+                    write!(f, "The simplex {} is not in the complex", simplex)
+                }
+                ZigzagError::MissingFace { simplex, face } => {
+                    write!(f, "The face {} of {} is not in the complex", face, simplex)
+                }
+                ZigzagError::CofacePresent { simplex, coface } => {
+                    write!(
+                        f,
+                        "The simplex {} cannot be removed before its coface {}",
+                        simplex, coface
+                    )
+                }
+            }
+        }
+    }
+
+    impl std::error::Error for ZigzagError {}
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum ZigzagOperation {
+        Insertion,
+        Deletion,
+    }
+
+    // A zigzag of simplicial complexes, given as a sequence of insertions and deletions of
+    // single simplices at nondecreasing times. A simplex inserted at time t is present from
+    // t on, a simplex deleted at time t is present up to and including t. Every complex of
+    // the sequence must be a simplicial complex, so faces are inserted before and deleted
+    // after their cofaces.
+    #[derive(Debug, Clone)]
+    pub struct ZigzagFiltration<T> {
+        operations: Vec<(Simplex, ZigzagOperation, T)>,
+        current_complex: HashSet<Simplex>,
+    }
+
+    impl<T> ZigzagFiltration<T>
+    where
+        T: Clone + Debug + PartialOrd,
+    {
+        pub fn new() -> Self {
+            Self {
+                operations: Vec::new(),
+                current_complex: HashSet::new(),
+            }
+        }
+
+        pub fn insert(&mut self, simplex: Simplex, time: T) -> Result<(), ZigzagError> {
+            self.check_time(&time)?;
+
+            if self.current_complex.contains(&simplex) {
+                return Err(ZigzagError::AlreadyPresent {
+                    simplex: simplex.to_string(),
+                });
+            }
+
+            if let Some(face) = simplex
+                .get_boundary_array()
+                .into_iter()
+                .find(|face| !self.current_complex.contains(face))
+            {
+                return Err(ZigzagError::MissingFace {
+                    simplex: simplex.to_string(),
+                    face: face.to_string(),
+                });
+            }
+
+            self.current_complex.insert(simplex.clone());
+            self.operations
+                .push((simplex, ZigzagOperation::Insertion, time));
+
+            return Ok(());
+        }
+
+        pub fn delete(&mut self, simplex: &Simplex, time: T) -> Result<(), ZigzagError> {
+            self.check_time(&time)?;
+
+            if !self.current_complex.contains(simplex) {
+                return Err(ZigzagError::NotPresent {
+                    simplex: simplex.to_string(),
+                });
+            }
+
+            if let Some(coface) = self
+                .current_complex
+                .iter()
+                .find(|s| *s != simplex && simplex.is_face_of(s))
+            {
+                return Err(ZigzagError::CofacePresent {
+                    simplex: simplex.to_string(),
+                    coface: coface.to_string(),
+                });
+            }
+
+            self.current_complex.remove(simplex);
+            self.operations
+                .push((simplex.clone(), ZigzagOperation::Deletion, time));
+
+            return Ok(());
+        }
+
+        pub fn contains(&self, simplex: &Simplex) -> bool {
+            return self.current_complex.contains(simplex);
+        }
+
+        pub fn get_size(&self) -> usize {
+            return self.operations.len();
+        }
+
+        fn check_time(&self, time: &T) -> Result<(), ZigzagError> {
+            return match self.operations.last() {
+                Some((_, _, last_time)) if time < last_time => Err(ZigzagError::DecreasingTime),
+                _ => Ok(()),
+            };
+        }
+    }
+
+    impl<T> Default for ZigzagFiltration<T>
+    where
+        T: Clone + Debug + PartialOrd,
+    {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    // Birth or death of a class, with the index of the operation causing it. Simplices
+    // still present at the end are deleted by operations past the end (None).
+    #[derive(Debug, Clone, Copy)]
+    enum Event {
+        Insertion(usize),
+        Deletion(Option<usize>),
+    }
+
+    impl Event {
+        fn get_position(&self) -> usize {
+            return match self {
+                Event::Insertion(k) | Event::Deletion(Some(k)) => *k,
+                Event::Deletion(None) => usize::MAX,
+            };
+        }
+    }
+
+    // The deleted cells coned off in reverse order of deletion, the first one deleted
+    // entering last
+    struct DeletionStream<'a> {
+        cells: &'a ExplicitCellStream<usize>,
+        coned_cells: Vec<Cell>,
+        filtration_values: HashMap<Cell, usize>,
+    }
+
+    impl FilteredStream<Cell, usize> for DeletionStream<'_> {
+        fn finalize_stream(&mut self) -> Result<(), StreamError> {
+            return Ok(());
+        }
+
+        fn is_finalized(&self) -> bool {
+            return true;
+        }
+
+        fn get_size(&self) -> usize {
+            return self.coned_cells.len();
+        }
+
+        fn iter(&self) -> std::slice::Iter<'_, Cell> {
+            return self.coned_cells.iter();
+        }
+
+        fn get_filtration_value(&self, element: &Cell) -> Option<usize> {
+            return self.filtration_values.get(element).cloned();
+        }
+
+        fn get_dimension(&self, element: &Cell) -> u32 {
+            return element.get_dimension();
+        }
+
+        fn get_boundary(&self, element: &Cell) -> Vec<(Cell, i64)> {
+            return self.cells.get_boundary(element);
+        }
+    }
+
+    // Zigzag persistent homology over the field F, following the reduction of Dey and Hou
+    // (FastZigzag). Every insertion creates a new copy of its simplex in a Delta-complex K,
+    // and the zigzag is rearranged into the up-down sequence inserting all copies first and
+    // deleting them afterwards. By the diamond principle the barcodes of both sequences
+    // correspond, and the barcode of the up-down sequence is the extended persistence of K
+    // relative to the deleted copies, computed by RelativeHomology. Intervals are computed
+    // for the dimensions 0, ..., max_dimension - 1, classes being born at closed ends by
+    // insertions and at open ends by deletions, and dying at open ends by insertions and at
+    // closed ends by deletions.
+    pub struct ZigzagPersistence<F> {
+        field: F,
+        max_dimension: u32,
+    }
+
+    impl<F> ZigzagPersistence<F>
+    where
+        F: Field,
+    {
+        pub fn new(field: F, max_dimension: u32) -> Self {
+            Self {
+                field,
+                max_dimension,
+            }
+        }
+
+        pub fn compute_intervals<T, G>(
+            &self,
+            filtration: &ZigzagFiltration<T>,
+        ) -> BarcodeCollection<T, G>
+        where
+            T: Clone + Debug + PartialEq + PartialOrd,
+            G: Clone + Debug + Default,
+        {
+            let mut result: AnnotatedBarcodeCollection<T, G> =
+                AnnotatedBarcodeCollection::default();
+            let mut cells: ExplicitCellStream<usize> = ExplicitCellStream::new();
+            let mut copies: HashMap<Simplex, Cell> = HashMap::new();
+            let mut insertions: Vec<(Cell, usize)> = Vec::new();
+            let mut deletions: Vec<(Cell, Option<usize>)> = Vec::new();
+
+            for (k, (simplex, operation, _)) in filtration.operations.iter().enumerate() {
+                match operation {
+                    ZigzagOperation::Insertion => {
+                        let boundary: Vec<(Cell, i64)> = simplex
+                            .get_boundary()
+                            .into_iter()
+                            .map(|(face, c)| (copies[&face], c))
+                            .collect();
+                        let cell: Cell = cells
+                            .add_cell(simplex.get_dimension(), &boundary, insertions.len() + 1)
+                            .expect("Zigzag complexes are always valid cell complexes");
+
+                        copies.insert(simplex.clone(), cell);
+                        insertions.push((cell, k));
+                    }
+                    ZigzagOperation::Deletion => {
+                        deletions.push((copies.remove(simplex).unwrap(), Some(k)));
+                    }
+                }
+            }
+
+            // Cofaces are deleted first at the end, i.e. by decreasing dimension
+            let mut remaining: Vec<Cell> = copies.into_values().collect();
+            remaining.sort_by(|a, b| b.cmp(a));
+            deletions.extend(remaining.into_iter().map(|cell| (cell, None)));
+            cells.finalize_stream().unwrap();
+
+            let n: usize = insertions.len();
+
+            if n == 0 {
+                return BarcodeCollection::new(result);
+            }
+
+            // Up to time n the copies are inserted, then the j-th deleted copy is coned off
+            // at time 2n - j + 1, so that the pair at time 2n - j is (K, D_j) with D_j the
+            // copies left after the first j deletions
+            let subcomplex: DeletionStream = DeletionStream {
+                cells: &cells,
+                coned_cells: deletions.iter().rev().map(|(cell, _)| *cell).collect(),
+                filtration_values: deletions
+                    .iter()
+                    .enumerate()
+                    .map(|(j, (cell, _))| (*cell, 2 * n - j))
+                    .collect(),
+            };
+            let extended: BarcodeCollection<usize, ()> =
+                RelativeHomology::new(self.field.clone(), self.max_dimension + 1)
+                    .compute_intervals(&cells, &subcomplex)
+                    .expect("The deleted copies always form a subcomplex");
+
+            // Event of every arrow of the up-down sequence, arrow a being events[a - 1]
+            let events: Vec<Event> = insertions
+                .iter()
+                .map(|(_, k)| Event::Insertion(*k))
+                .chain(deletions.iter().map(|(_, k)| Event::Deletion(*k)))
+                .collect();
+
+            for dimension in extended.get_dimensions() {
+                for interval in extended.get_intervals_at_dimension(dimension) {
+                    let birth: usize = interval.clone().get_start().unwrap();
+                    let death: usize = interval.get_end().unwrap();
+
+                    // The interval [first, last] of the up-down sequence. Relative classes
+                    // of the down part have their dimension lowered by one.
+                    let (dimension, first, last): (Option<u32>, usize, usize) = if death <= n {
+                        (Some(dimension), birth, death - 1)
+                    } else if birth <= n {
+                        (Some(dimension), birth, 3 * n - death)
+                    } else {
+                        (dimension.checked_sub(1), 3 * n - death + 1, 3 * n - birth)
+                    };
+                    let (mut dimension, mut birth_event, mut death_event): (
+                        Option<u32>,
+                        Event,
+                        Event,
+                    ) = (dimension, events[first - 1], events[last]);
+
+                    // A class born by an insertion and dying by an earlier deletion in the
+                    // up-down sequence comes from a class one dimension lower in the zigzag,
+                    // born by the deletion and dying by the insertion
+                    if let (Event::Insertion(_), Event::Deletion(_)) = (birth_event, death_event) {
+                        if death_event.get_position() < birth_event.get_position() {
+                            dimension = dimension.and_then(|d| d.checked_sub(1));
+                            (birth_event, death_event) = (death_event, birth_event);
+                        }
+                    }
+
+                    if let Some(dimension) = dimension.filter(|d| *d < self.max_dimension) {
+                        if let Some(interval) =
+                            Self::to_interval(filtration, birth_event, death_event)
+                        {
+                            result.add_exact_interval(dimension, interval, None);
+                        }
+                    }
+                }
+            }
+
+            return BarcodeCollection::new(result);
+        }
+
+        fn to_interval<T>(
+            filtration: &ZigzagFiltration<T>,
+            birth_event: Event,
+            death_event: Event,
+        ) -> Option<Interval<T>>
+        where
+            T: Clone + Debug + PartialEq + PartialOrd,
+        {
+            let (start, is_left_closed): (T, bool) = match birth_event {
+                Event::Insertion(k) => (filtration.operations[k].2.clone(), true),
+                Event::Deletion(Some(k)) => (filtration.operations[k].2.clone(), false),
+                // Classes born after the end of the zigzag never exist
+                Event::Deletion(None) => return None,
+            };
+
+            let interval: Interval<T> = match death_event {
+                Event::Insertion(k) => Interval::new(
+                    Some(start),
+                    Some(filtration.operations[k].2.clone()),
+                    is_left_closed,
+                    false,
+                    false,
+                    false,
+                ),
+                Event::Deletion(Some(k)) => Interval::new(
+                    Some(start),
+                    Some(filtration.operations[k].2.clone()),
+                    is_left_closed,
+                    true,
+                    false,
+                    false,
+                ),
+                Event::Deletion(None) => {
+                    Interval::new(Some(start), None, is_left_closed, false, false, true)
+                }
+            };
+
+            // Classes created and destroyed at the same time, e.g. [t, t), are dropped
+            return (!interval.is_empty()).then_some(interval);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::zigzag_persistence::{ZigzagError, ZigzagFiltration, ZigzagPersistence};
+    use crate::barcode_collections::barcode_collections::BarcodeCollection;
+    use crate::fields::fields::{BooleanField, ModularIntField};
+    use crate::intervals::intervals::Interval;
+    use crate::simplex::simplex::Simplex;
+
+    fn sorted_intervals(
+        barcodes: &BarcodeCollection<u32, ()>,
+        dimension: u32,
+    ) -> Vec<Interval<u32>> {
+        let mut intervals: Vec<Interval<u32>> = barcodes.get_intervals_at_dimension(dimension);
+        intervals.sort_by(|a, b| a.compare_lexicographic(b));
+
+        return intervals;
+    }
+
+    fn insert_triangle_boundary(filtration: &mut ZigzagFiltration<u32>) {
+        filtration.insert(Simplex::vertex(0), 1).unwrap();
+        filtration.insert(Simplex::vertex(1), 2).unwrap();
+        filtration.insert(Simplex::vertex(2), 3).unwrap();
+        filtration.insert(Simplex::new(&[0, 1]), 4).unwrap();
+        filtration.insert(Simplex::new(&[1, 2]), 5).unwrap();
+    }
+
+    #[test]
+    fn test_circle_appearing_and_disappearing() {
+        let mut filtration: ZigzagFiltration<u32> = ZigzagFiltration::new();
+        insert_triangle_boundary(&mut filtration);
+        filtration.insert(Simplex::new(&[0, 2]), 6).unwrap();
+        filtration.delete(&Simplex::new(&[0, 2]), 7).unwrap();
+        filtration.delete(&Simplex::new(&[1, 2]), 8).unwrap();
+        filtration.delete(&Simplex::new(&[0, 1]), 9).unwrap();
+        filtration.delete(&Simplex::vertex(2), 10).unwrap();
+        filtration.delete(&Simplex::vertex(1), 11).unwrap();
+        filtration.delete(&Simplex::vertex(0), 12).unwrap();
+
+        for prime in [2, 3] {
+            let barcodes: BarcodeCollection<u32, ()> =
+                ZigzagPersistence::new(ModularIntField::new(prime), 2)
+                    .compute_intervals(&filtration);
+
+            assert_eq!(
+                sorted_intervals(&barcodes, 0),
+                vec![
+                    Interval::finite_closed_interval(1, 12),
+                    Interval::finite_right_open_interval(2, 4),
+                    Interval::finite_right_open_interval(3, 5),
+                    Interval::finite_left_open_interval(8, 10),
+                    Interval::finite_left_open_interval(9, 11),
+                ]
+            );
+            assert_eq!(
+                sorted_intervals(&barcodes, 1),
+                vec![Interval::finite_closed_interval(6, 7)]
+            );
+        }
+    }
+
+    #[test]
+    fn test_class_born_by_deletion_and_killed_by_insertion() {
+        let mut filtration: ZigzagFiltration<u32> = ZigzagFiltration::new();
+        insert_triangle_boundary(&mut filtration);
+        filtration.delete(&Simplex::new(&[0, 1]), 6).unwrap();
+        filtration.insert(Simplex::new(&[0, 2]), 7).unwrap();
+
+        let barcodes: BarcodeCollection<u32, ()> =
+            ZigzagPersistence::new(BooleanField, 2).compute_intervals(&filtration);
+
+        // The vertex 0 is cut off at 6 and reattached at 7, and nothing dies at the end
+        assert_eq!(
+            sorted_intervals(&barcodes, 0),
+            vec![
+                Interval::right_infinite_right_open_interval(1),
+                Interval::finite_right_open_interval(2, 4),
+                Interval::finite_right_open_interval(3, 5),
+                Interval::finite_open_interval(6, 7),
+            ]
+        );
+        assert!(barcodes.get_intervals_at_dimension(1).is_empty());
+    }
+
+    #[test]
+    fn test_reinserted_simplices_and_simultaneous_operations() {
+        // A loop closed twice, with everything at time 0 merging without leaving a trace
+        let mut filtration: ZigzagFiltration<u32> = ZigzagFiltration::new();
+        for vertex in 0..3 {
+            filtration.insert(Simplex::vertex(vertex), 0).unwrap();
+        }
+        filtration.insert(Simplex::new(&[0, 1]), 0).unwrap();
+        filtration.insert(Simplex::new(&[1, 2]), 0).unwrap();
+        filtration.insert(Simplex::new(&[0, 2]), 1).unwrap();
+        filtration.delete(&Simplex::new(&[0, 2]), 2).unwrap();
+        filtration.insert(Simplex::new(&[0, 2]), 3).unwrap();
+
+        let barcodes: BarcodeCollection<u32, ()> =
+            ZigzagPersistence::new(BooleanField, 2).compute_intervals(&filtration);
+
+        assert_eq!(
+            sorted_intervals(&barcodes, 0),
+            vec![Interval::right_infinite_right_open_interval(0)]
+        );
+        assert_eq!(
+            sorted_intervals(&barcodes, 1),
+            vec![
+                Interval::finite_closed_interval(1, 2),
+                Interval::right_infinite_right_open_interval(3),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_operations() {
+        let mut filtration: ZigzagFiltration<u32> = ZigzagFiltration::new();
+        filtration.insert(Simplex::vertex(0), 1).unwrap();
+        filtration.insert(Simplex::vertex(1), 1).unwrap();
+        filtration.insert(Simplex::new(&[0, 1]), 2).unwrap();
+
+        assert!(matches!(
+            filtration.insert(Simplex::new(&[1, 2]), 3),
+            Err(ZigzagError::MissingFace { .. })
+        ));
+        assert!(matches!(
+            filtration.insert(Simplex::vertex(0), 3),
+            Err(ZigzagError::AlreadyPresent { .. })
+        ));
+        assert!(matches!(
+            filtration.delete(&Simplex::vertex(0), 3),
+            Err(ZigzagError::CofacePresent { .. })
+        ));
+        assert!(matches!(
+            filtration.delete(&Simplex::vertex(2), 3),
+            Err(ZigzagError::NotPresent { .. })
+        ));
+        assert_eq!(
+            filtration.delete(&Simplex::new(&[0, 1]), 1),
+            Err(ZigzagError::DecreasingTime)
+        );
+        assert_eq!(filtration.get_size(), 3);
+        assert!(ZigzagPersistence::new(BooleanField, 2)
+            .compute_intervals::<u32, ()>(&ZigzagFiltration::new())
+            .get_dimensions()
+            .is_empty());
+    }
+}
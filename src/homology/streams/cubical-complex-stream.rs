@@ -0,0 +1,373 @@
+#![allow(dead_code)]
+pub mod cubical_complex_stream {
+    use std::fmt::Debug;
+
+    use crate::absolute_homology::absolute_homology::AbsoluteHomology;
+    use crate::barcode_collections::barcode_collections::BarcodeCollection;
+    use crate::cube::cube::Cube;
+    use crate::fields::fields::Field;
+    use crate::filtered_stream::filtered_stream::{FilteredStream, StreamError};
+
+    // How the values of the grid become a sublevel filtration of a cubical complex
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum CubicalConstruction {
+        // V-construction: the values sit on the vertices, and every cube enters at the
+        // largest value of its vertices
+        Vertex,
+        // T-construction: the values sit on the top dimensional cubes (pixels, voxels), and
+        // every cube enters at the smallest value of the top cubes containing it
+        Top,
+    }
+
+    // The sublevel filtration of a cubical complex built from an n-dimensional grid of
+    // values, e.g. an image or a volume. Values are given in row-major order, the last axis
+    // varying fastest. The cubes are constructed when the stream is finalized.
+    pub struct CubicalComplexStream {
+        shape: Vec<usize>,
+        values: Vec<f64>,
+        construction: CubicalConstruction,
+        // Extent of the complex along every axis, in doubled coordinates
+        extents: Vec<usize>,
+        cubes: Vec<Cube>,
+        filtration_values: Vec<f64>,
+        is_finalized: bool,
+    }
+
+    impl CubicalComplexStream {
+        pub fn new(shape: &[usize], values: Vec<f64>, construction: CubicalConstruction) -> Self {
+            assert!(
+                !shape.is_empty() && shape.iter().all(|s| *s > 0),
+                "The grid must have at least one axis and no empty axis"
+            );
+            assert_eq!(
+                shape.iter().product::<usize>(),
+                values.len(),
+                "The number of values must match the shape of the grid"
+            );
+            assert!(
+                values.iter().all(|v| !v.is_nan()),
+                "The values must not be NaN"
+            );
+
+            // Adding zero turns -0 into 0, so that total_cmp keeps equal values together
+            let values: Vec<f64> = values.into_iter().map(|v| v + 0.0).collect();
+
+            let extents: Vec<usize> = shape
+                .iter()
+                .map(|s| match construction {
+                    CubicalConstruction::Vertex => 2 * s - 1,
+                    CubicalConstruction::Top => 2 * s + 1,
+                })
+                .collect();
+
+            Self {
+                shape: shape.to_vec(),
+                values,
+                construction,
+                extents,
+                cubes: Vec::new(),
+                filtration_values: Vec::new(),
+                is_finalized: false,
+            }
+        }
+
+        // A two dimensional image given as its rows
+        pub fn from_image(rows: &[Vec<f64>], construction: CubicalConstruction) -> Self {
+            let width: usize = rows.first().map_or(0, |row| row.len());
+            assert!(
+                rows.iter().all(|row| row.len() == width),
+                "All rows of the image must have the same length"
+            );
+
+            return Self::new(&[rows.len(), width], rows.concat(), construction);
+        }
+
+        pub fn get_shape(&self) -> &[usize] {
+            return &self.shape;
+        }
+
+        pub fn get_construction(&self) -> CubicalConstruction {
+            return self.construction;
+        }
+
+        // Persistence in every dimension below the dimension of the grid, over the field
+        pub fn compute_intervals<F, G>(&mut self, field: F) -> BarcodeCollection<f64, G>
+        where
+            F: Field,
+            G: Clone + Debug + Default,
+        {
+            self.finalize_stream()
+                .expect("A cubical complex is always a valid stream");
+
+            return AbsoluteHomology::new(field, self.shape.len() as u32).compute_intervals(self);
+        }
+
+        fn get_index(&self, coordinates: &[u32]) -> usize {
+            return coordinates
+                .iter()
+                .zip(self.extents.iter())
+                .fold(0, |index, (c, extent)| index * extent + *c as usize);
+        }
+
+        fn get_value(&self, point: &[u32]) -> f64 {
+            let index: usize = point
+                .iter()
+                .zip(self.shape.iter())
+                .fold(0, |index, (c, size)| index * size + *c as usize);
+
+            return self.values[index];
+        }
+
+        fn compute_filtration_value(&self, cube: &Cube) -> f64 {
+            return match self.construction {
+                CubicalConstruction::Vertex => cube
+                    .get_vertices()
+                    .iter()
+                    .map(|v| self.get_value(v))
+                    .fold(f64::NEG_INFINITY, f64::max),
+                CubicalConstruction::Top => {
+                    // Along an axis where the cube is a point, the top cubes containing it
+                    // lie on either side of it, when inside the grid
+                    let mut pixels: Vec<Vec<u32>> = vec![Vec::new()];
+
+                    for (axis, c) in cube.get_coordinates().iter().enumerate() {
+                        let mut choices: Vec<u32> = Vec::new();
+
+                        if c % 2 == 1 {
+                            choices.push(c / 2);
+                        } else {
+                            if *c > 0 {
+                                choices.push(c / 2 - 1);
+                            }
+                            if ((c / 2) as usize) < self.shape[axis] {
+                                choices.push(c / 2);
+                            }
+                        }
+
+                        pixels = pixels
+                            .into_iter()
+                            .flat_map(|p| {
+                                choices.iter().map(move |choice| {
+                                    let mut pixel: Vec<u32> = p.clone();
+                                    pixel.push(*choice);
+                                    pixel
+                                })
+                            })
+                            .collect();
+                    }
+
+                    pixels
+                        .iter()
+                        .map(|p| self.get_value(p))
+                        .fold(f64::INFINITY, f64::min)
+                }
+            };
+        }
+
+        fn construct_cubes(&mut self) {
+            let num_cubes: usize = self.extents.iter().product();
+            let mut cubes: Vec<Cube> = Vec::with_capacity(num_cubes);
+            let mut filtration_values: Vec<f64> = Vec::with_capacity(num_cubes);
+
+            for index in 0..num_cubes {
+                let mut coordinates: Vec<u32> = vec![0; self.extents.len()];
+                let mut remainder: usize = index;
+
+                for (axis, extent) in self.extents.iter().enumerate().rev() {
+                    coordinates[axis] = (remainder % extent) as u32;
+                    remainder /= extent;
+                }
+
+                let cube: Cube = Cube::new(&coordinates);
+                filtration_values.push(self.compute_filtration_value(&cube));
+                cubes.push(cube);
+            }
+
+            // Faces never enter later than their cofaces, and ties are broken by dimension
+            cubes.sort_by(|a, b| {
+                filtration_values[self.get_index(a.get_coordinates())]
+                    .total_cmp(&filtration_values[self.get_index(b.get_coordinates())])
+                    .then_with(|| a.cmp(b))
+            });
+
+            self.cubes = cubes;
+            self.filtration_values = filtration_values;
+        }
+    }
+
+    impl FilteredStream<Cube, f64> for CubicalComplexStream {
+        fn finalize_stream(&mut self) -> Result<(), StreamError> {
+            if self.is_finalized {
+                return Ok(());
+            }
+
+            self.construct_cubes();
+            self.is_finalized = true;
+
+            return Ok(());
+        }
+
+        fn is_finalized(&self) -> bool {
+            return self.is_finalized;
+        }
+
+        fn get_size(&self) -> usize {
+            return self.cubes.len();
+        }
+
+        fn iter(&self) -> std::slice::Iter<'_, Cube> {
+            return self.cubes.iter();
+        }
+
+        fn get_filtration_value(&self, element: &Cube) -> Option<f64> {
+            let coordinates: &[u32] = element.get_coordinates();
+
+            if !self.is_finalized
+                || coordinates.len() != self.extents.len()
+                || coordinates
+                    .iter()
+                    .zip(self.extents.iter())
+                    .any(|(c, extent)| *c as usize >= *extent)
+            {
+                return None;
+            }
+
+            return Some(self.filtration_values[self.get_index(coordinates)]);
+        }
+
+        fn get_dimension(&self, element: &Cube) -> u32 {
+            return element.get_dimension();
+        }
+
+        fn get_boundary(&self, element: &Cube) -> Vec<(Cube, i64)> {
+            return element.get_boundary();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::cubical_complex_stream::{CubicalComplexStream, CubicalConstruction};
+    use crate::barcode_collections::barcode_collections::BarcodeCollection;
+    use crate::cube::cube::Cube;
+    use crate::fields::fields::{BooleanField, ModularIntField};
+    use crate::filtered_stream::filtered_stream::FilteredStream;
+    use crate::intervals::intervals::Interval;
+
+    fn sorted_intervals(
+        barcodes: &BarcodeCollection<f64, ()>,
+        dimension: u32,
+    ) -> Vec<Interval<f64>> {
+        let mut intervals: Vec<Interval<f64>> = barcodes.get_intervals_at_dimension(dimension);
+        intervals.sort_by(|a, b| a.compare_lexicographic(b));
+
+        return intervals;
+    }
+
+    #[test]
+    fn test_filtration_values_of_both_constructions() {
+        let rows: Vec<Vec<f64>> = vec![vec![1.0, 2.0], vec![3.0, 4.0]];
+
+        let mut vertex: CubicalComplexStream =
+            CubicalComplexStream::from_image(&rows, CubicalConstruction::Vertex);
+        vertex.finalize_stream().unwrap();
+
+        // Vertices at the grid points, one square
+        assert_eq!(vertex.get_size(), 9);
+        assert_eq!(
+            vertex.get_filtration_value(&Cube::vertex(&[1, 0])),
+            Some(3.0)
+        );
+        assert_eq!(vertex.get_filtration_value(&Cube::new(&[1, 0])), Some(3.0));
+        assert_eq!(vertex.get_filtration_value(&Cube::new(&[1, 1])), Some(4.0));
+        assert_eq!(vertex.get_filtration_value(&Cube::new(&[3, 1])), None);
+        assert_eq!(vertex.iter().next(), Some(&Cube::vertex(&[0, 0])));
+
+        let mut top: CubicalComplexStream =
+            CubicalComplexStream::from_image(&rows, CubicalConstruction::Top);
+        top.finalize_stream().unwrap();
+
+        // One square per pixel, its faces taking the smallest neighbouring value
+        assert_eq!(top.get_size(), 25);
+        assert_eq!(top.get_filtration_value(&Cube::new(&[3, 1])), Some(3.0));
+        assert_eq!(top.get_filtration_value(&Cube::new(&[2, 2])), Some(1.0));
+        assert_eq!(top.get_filtration_value(&Cube::new(&[4, 3])), Some(4.0));
+        assert_eq!(top.get_filtration_value(&Cube::new(&[2, 3])), Some(2.0));
+    }
+
+    #[test]
+    fn test_one_dimensional_signal() {
+        let values: Vec<f64> = vec![0.0, 3.0, 1.0, 4.0, 0.0];
+
+        for construction in [CubicalConstruction::Vertex, CubicalConstruction::Top] {
+            let barcodes: BarcodeCollection<f64, ()> =
+                CubicalComplexStream::new(&[5], values.clone(), construction)
+                    .compute_intervals(BooleanField);
+
+            assert_eq!(
+                sorted_intervals(&barcodes, 0),
+                vec![
+                    Interval::finite_right_open_interval(0.0, 4.0),
+                    Interval::right_infinite_right_open_interval(0.0),
+                    Interval::finite_right_open_interval(1.0, 3.0),
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn test_signed_zeros_keep_faces_first() {
+        for construction in [CubicalConstruction::Vertex, CubicalConstruction::Top] {
+            let mut stream: CubicalComplexStream =
+                CubicalComplexStream::new(&[2, 2], vec![0.0, -0.0, -0.0, 0.0], construction);
+            stream.finalize_stream().unwrap();
+
+            let position = |cube: &Cube| stream.iter().position(|c| c == cube).unwrap();
+            for cube in stream.iter() {
+                for (face, _) in stream.get_boundary(cube) {
+                    assert!(position(&face) < position(cube));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_image_with_a_hole() {
+        let rows: Vec<Vec<f64>> = vec![
+            vec![0.0, 0.0, 0.0],
+            vec![0.0, 5.0, 0.0],
+            vec![0.0, 0.0, 0.0],
+        ];
+
+        for construction in [CubicalConstruction::Vertex, CubicalConstruction::Top] {
+            let barcodes: BarcodeCollection<f64, ()> =
+                CubicalComplexStream::from_image(&rows, construction)
+                    .compute_intervals(ModularIntField::new(3));
+
+            assert_eq!(
+                sorted_intervals(&barcodes, 0),
+                vec![Interval::right_infinite_right_open_interval(0.0)]
+            );
+            assert_eq!(
+                sorted_intervals(&barcodes, 1),
+                vec![Interval::finite_right_open_interval(0.0, 5.0)]
+            );
+        }
+    }
+
+    #[test]
+    fn test_volume_with_a_cavity() {
+        let mut values: Vec<f64> = vec![0.0; 27];
+        values[13] = 2.0;
+
+        let barcodes: BarcodeCollection<f64, ()> =
+            CubicalComplexStream::new(&[3, 3, 3], values, CubicalConstruction::Top)
+                .compute_intervals(BooleanField);
+
+        assert_eq!(barcodes.get_dimensions(), vec![0, 2]);
+        assert_eq!(
+            sorted_intervals(&barcodes, 2),
+            vec![Interval::finite_right_open_interval(0.0, 2.0)]
+        );
+    }
+}
@@ -0,0 +1,278 @@
+#![allow(dead_code)]
+pub mod explicit_cell_stream {
+    use std::cmp::Ordering::Equal;
+    use std::collections::HashMap;
+    use std::fmt::Debug;
+
+    use crate::cell::cell::Cell;
+    use crate::filtered_stream::filtered_stream::{FilteredStream, StreamError};
+
+    // A filtered CW complex built up by hand, analogue of JavaPlex's ExplicitCellStream.
+    // Every cell is attached along an integer combination of cells of one dimension lower,
+    // which lets spaces such as the projective plane be built from a handful of cells. Since
+    // faces must exist before their cofaces, cells are validated as they are added.
+    #[derive(Debug, Clone)]
+    pub struct ExplicitCellStream<T> {
+        cells: Vec<Cell>,
+        filtration_values: HashMap<Cell, T>,
+        boundaries: HashMap<Cell, Vec<(Cell, i64)>>,
+        is_finalized: bool,
+    }
+
+    impl<T> ExplicitCellStream<T>
+    where
+        T: Clone + Debug + PartialOrd,
+    {
+        pub fn new() -> Self {
+            Self {
+                cells: Vec::new(),
+                filtration_values: HashMap::new(),
+                boundaries: HashMap::new(),
+                is_finalized: false,
+            }
+        }
+
+        pub fn add_vertex(&mut self, filtration_value: T) -> Result<Cell, StreamError> {
+            return self.add_cell(0, &[], filtration_value);
+        }
+
+        // Adds a cell with the given boundary, a list of faces with their coefficients, and
+        // returns it. Repeated faces are summed.
+        pub fn add_cell(
+            &mut self,
+            dimension: u32,
+            boundary: &[(Cell, i64)],
+            filtration_value: T,
+        ) -> Result<Cell, StreamError> {
+            if self.is_finalized {
+                return Err(StreamError::AlreadyFinalized);
+            }
+
+            let cell: Cell = Cell::new(self.cells.len() as u32, dimension);
+
+            // Values like NaN cannot be placed in the filtration
+            if filtration_value.partial_cmp(&filtration_value).is_none() {
+                return Err(StreamError::UnorderedFiltrationValue {
+                    element: cell.to_string(),
+                });
+            }
+            let mut squared_boundary: HashMap<Cell, i64> = HashMap::new();
+
+            for (face, coefficient) in boundary.iter() {
+                let face_value: &T =
+                    self.filtration_values
+                        .get(face)
+                        .ok_or_else(|| StreamError::MissingFace {
+                            element: cell.to_string(),
+                            face: face.to_string(),
+                        })?;
+
+                if face.get_dimension() + 1 != dimension {
+                    return Err(StreamError::FaceDimensionMismatch {
+                        element: cell.to_string(),
+                        face: face.to_string(),
+                    });
+                }
+
+                if *face_value > filtration_value {
+                    return Err(StreamError::FaceAfterCoface {
+                        element: cell.to_string(),
+                        face: face.to_string(),
+                    });
+                }
+
+                for (ridge, ridge_coefficient) in self.boundaries[face].iter() {
+                    *squared_boundary.entry(*ridge).or_insert(0) += coefficient * ridge_coefficient;
+                }
+            }
+
+            if squared_boundary.values().any(|c| *c != 0) {
+                return Err(StreamError::NonZeroSquaredBoundary {
+                    element: cell.to_string(),
+                });
+            }
+
+            self.cells.push(cell);
+            self.filtration_values.insert(cell, filtration_value);
+            self.boundaries.insert(cell, boundary.to_vec());
+
+            return Ok(cell);
+        }
+
+        pub fn contains(&self, cell: &Cell) -> bool {
+            return self.filtration_values.contains_key(cell);
+        }
+    }
+
+    impl<T> FilteredStream<Cell, T> for ExplicitCellStream<T>
+    where
+        T: Clone + Debug + PartialOrd,
+    {
+        fn finalize_stream(&mut self) -> Result<(), StreamError> {
+            if self.is_finalized {
+                return Ok(());
+            }
+
+            // Ties in filtration value are broken by dimension, so faces always come first.
+            // Unordered values were rejected when adding the cells.
+            let filtration_values: &HashMap<Cell, T> = &self.filtration_values;
+            self.cells.sort_by(|a, b| {
+                filtration_values
+                    .get(a)
+                    .partial_cmp(&filtration_values.get(b))
+                    .unwrap_or(Equal)
+                    .then_with(|| a.cmp(b))
+            });
+            self.is_finalized = true;
+
+            return Ok(());
+        }
+
+        fn is_finalized(&self) -> bool {
+            return self.is_finalized;
+        }
+
+        fn get_size(&self) -> usize {
+            return self.cells.len();
+        }
+
+        fn iter(&self) -> std::slice::Iter<'_, Cell> {
+            return self.cells.iter();
+        }
+
+        fn get_filtration_value(&self, element: &Cell) -> Option<T> {
+            return self.filtration_values.get(element).cloned();
+        }
+
+        fn get_dimension(&self, element: &Cell) -> u32 {
+            return element.get_dimension();
+        }
+
+        fn get_boundary(&self, element: &Cell) -> Vec<(Cell, i64)> {
+            return self.boundaries.get(element).cloned().unwrap_or_default();
+        }
+    }
+
+    impl<T> Default for ExplicitCellStream<T>
+    where
+        T: Clone + Debug + PartialOrd,
+    {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::explicit_cell_stream::ExplicitCellStream;
+    use crate::absolute_homology::absolute_homology::AbsoluteHomology;
+    use crate::barcode_collections::barcode_collections::{
+        AnnotatedBarcodeCollection, BarcodeCollection,
+    };
+    use crate::cell::cell::Cell;
+    use crate::chain::chain::Chain;
+    use crate::fields::fields::{BooleanField, ModularIntField, Rational, RationalField};
+    use crate::filtered_stream::filtered_stream::{FilteredStream, StreamError};
+    use crate::intervals::intervals::Interval;
+
+    // One vertex, one loop and a disk attached along twice the loop
+    fn projective_plane_stream() -> ExplicitCellStream<u32> {
+        let mut stream: ExplicitCellStream<u32> = ExplicitCellStream::new();
+        let vertex: Cell = stream.add_vertex(0).unwrap();
+        let edge: Cell = stream.add_cell(1, &[(vertex, 1), (vertex, -1)], 1).unwrap();
+        stream.add_cell(2, &[(edge, 2)], 2).unwrap();
+        stream.finalize_stream().unwrap();
+
+        return stream;
+    }
+
+    #[test]
+    fn test_projective_plane() {
+        let stream: ExplicitCellStream<u32> = projective_plane_stream();
+
+        let mod_two: BarcodeCollection<u32, ()> =
+            AbsoluteHomology::new(BooleanField, 3).compute_intervals(&stream);
+        let rational: AnnotatedBarcodeCollection<u32, Chain<Cell, Rational>> =
+            AbsoluteHomology::new(RationalField, 3).compute_annotated_intervals(&stream);
+
+        assert_eq!(stream.get_size(), 3);
+        assert_eq!(
+            mod_two.get_intervals_at_dimension(1),
+            vec![Interval::right_infinite_right_open_interval(1)]
+        );
+        assert_eq!(
+            mod_two.get_intervals_at_dimension(2),
+            vec![Interval::right_infinite_right_open_interval(2)]
+        );
+
+        // Over the rationals the disk kills the loop, which is its own representative
+        let pairs: Vec<(Interval<u32>, Chain<Cell, Rational>)> =
+            rational.get_interval_generator_pairs_at_dimension(1);
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0].0, Interval::finite_right_open_interval(1, 2));
+        assert_eq!(pairs[0].1.get_support(), vec![&Cell::new(1, 1)]);
+        assert!(rational.get_intervals_at_dimension(2).is_empty());
+    }
+
+    #[test]
+    fn test_klein_bottle() {
+        // The square with sides identified along the word a b a b^-1
+        let mut stream: ExplicitCellStream<u32> = ExplicitCellStream::new();
+        let vertex: Cell = stream.add_vertex(0).unwrap();
+        let a: Cell = stream.add_cell(1, &[(vertex, 1), (vertex, -1)], 0).unwrap();
+        let b: Cell = stream.add_cell(1, &[(vertex, 1), (vertex, -1)], 0).unwrap();
+        stream
+            .add_cell(2, &[(a, 1), (b, 1), (a, 1), (b, -1)], 0)
+            .unwrap();
+        stream.finalize_stream().unwrap();
+
+        let mod_two: BarcodeCollection<u32, ()> =
+            AbsoluteHomology::new(BooleanField, 3).compute_intervals(&stream);
+        let mod_three: BarcodeCollection<u32, ()> =
+            AbsoluteHomology::new(ModularIntField::new(3), 3).compute_intervals(&stream);
+
+        assert_eq!(mod_two.get_intervals_at_dimension(0).len(), 1);
+        assert_eq!(mod_two.get_intervals_at_dimension(1).len(), 2);
+        assert_eq!(mod_two.get_intervals_at_dimension(2).len(), 1);
+        assert_eq!(mod_three.get_intervals_at_dimension(1).len(), 1);
+        assert!(mod_three.get_intervals_at_dimension(2).is_empty());
+    }
+
+    #[test]
+    fn test_invalid_cells_are_rejected() {
+        let mut stream: ExplicitCellStream<u32> = ExplicitCellStream::new();
+        let first: Cell = stream.add_vertex(0).unwrap();
+        let second: Cell = stream.add_vertex(1).unwrap();
+        let edge: Cell = stream.add_cell(1, &[(second, 1), (first, -1)], 1).unwrap();
+
+        assert!(matches!(
+            stream.add_cell(2, &[(edge, 1)], 2),
+            Err(StreamError::NonZeroSquaredBoundary { .. })
+        ));
+        assert!(matches!(
+            stream.add_cell(2, &[(first, 1)], 2),
+            Err(StreamError::FaceDimensionMismatch { .. })
+        ));
+        assert!(matches!(
+            stream.add_cell(1, &[(second, 1), (first, -1)], 0),
+            Err(StreamError::FaceAfterCoface { .. })
+        ));
+        assert!(matches!(
+            stream.add_cell(1, &[(Cell::new(7, 0), 1)], 2),
+            Err(StreamError::MissingFace { .. })
+        ));
+
+        // Rejected cells leave the stream untouched
+        assert_eq!(stream.get_size(), 3);
+        stream.finalize_stream().unwrap();
+        assert_eq!(stream.add_vertex(3), Err(StreamError::AlreadyFinalized));
+
+        let mut nan: ExplicitCellStream<f64> = ExplicitCellStream::new();
+        assert!(matches!(
+            nan.add_vertex(f64::NAN),
+            Err(StreamError::UnorderedFiltrationValue { .. })
+        ));
+        assert_eq!(nan.get_size(), 0);
+    }
+}
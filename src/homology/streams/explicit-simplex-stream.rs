@@ -0,0 +1,294 @@
+#![allow(dead_code)]
+pub mod explicit_simplex_stream {
+    use std::cmp::Ordering::Equal;
+    use std::collections::hash_map::Entry::{Occupied, Vacant};
+    use std::collections::{HashMap, HashSet};
+    use std::fmt::Debug;
+
+    use crate::filtered_stream::filtered_stream::{FilteredStream, StreamError};
+    use crate::simplex::simplex::Simplex;
+
+    // A filtered simplicial complex built up by hand, one simplex at a time.
+    #[derive(Debug, Clone)]
+    pub struct ExplicitSimplexStream<T> {
+        simplices: Vec<Simplex>,
+        filtration_values: HashMap<Simplex, T>,
+        is_finalized: bool,
+    }
+
+    impl<T> ExplicitSimplexStream<T>
+    where
+        T: Clone + Debug + PartialOrd,
+    {
+        pub fn new() -> Self {
+            Self {
+                simplices: Vec::new(),
+                filtration_values: HashMap::new(),
+                is_finalized: false,
+            }
+        }
+
+        pub fn add_vertex(&mut self, vertex: u32, filtration_value: T) -> Result<(), StreamError> {
+            self.add_simplex(Simplex::vertex(vertex), filtration_value)
+        }
+
+        pub fn add_element(
+            &mut self,
+            vertices: &[u32],
+            filtration_value: T,
+        ) -> Result<(), StreamError> {
+            self.add_simplex(Simplex::new(vertices), filtration_value)
+        }
+
+        pub fn add_simplex(
+            &mut self,
+            simplex: Simplex,
+            filtration_value: T,
+        ) -> Result<(), StreamError> {
+            if self.is_finalized {
+                return Err(StreamError::AlreadyFinalized);
+            }
+
+            // Values like NaN cannot be placed in the filtration
+            if filtration_value.partial_cmp(&filtration_value).is_none() {
+                return Err(StreamError::UnorderedFiltrationValue {
+                    element: simplex.to_string(),
+                });
+            }
+
+            match self.filtration_values.entry(simplex.clone()) {
+                Occupied(_) => {
+                    return Err(StreamError::DuplicateElement {
+                        element: simplex.to_string(),
+                    });
+                }
+                Vacant(e) => {
+                    e.insert(filtration_value);
+                }
+            }
+
+            self.simplices.push(simplex);
+
+            return Ok(());
+        }
+
+        pub fn contains(&self, simplex: &Simplex) -> bool {
+            return self.filtration_values.contains_key(simplex);
+        }
+
+        // Adds every missing face of every simplex in the stream, each entering at the
+        // earliest filtration value of the cofaces that require it.
+        pub fn ensure_all_faces(&mut self) -> Result<(), StreamError> {
+            if self.is_finalized {
+                return Err(StreamError::AlreadyFinalized);
+            }
+
+            // Simplices added by the user keep their filtration value, only the generated
+            // faces may be moved earlier when another coface requires it
+            let user_simplices: HashSet<Simplex> = self.simplices.iter().cloned().collect();
+            let mut pending: Vec<Simplex> = self.simplices.clone();
+
+            while let Some(simplex) = pending.pop() {
+                let filtration_value: T = self.filtration_values.get(&simplex).unwrap().clone();
+
+                for face in simplex.get_boundary_array() {
+                    match self.filtration_values.entry(face.clone()) {
+                        Occupied(mut e) => {
+                            if !user_simplices.contains(&face) && filtration_value < *e.get() {
+                                e.insert(filtration_value.clone());
+                                pending.push(face);
+                            }
+                        }
+                        Vacant(e) => {
+                            e.insert(filtration_value.clone());
+                            self.simplices.push(face.clone());
+                            pending.push(face);
+                        }
+                    }
+                }
+            }
+
+            return Ok(());
+        }
+
+        // Every face of every simplex must be present with a filtration value no later
+        // than that of the simplex itself.
+        pub fn validate(&self) -> Result<(), StreamError> {
+            for simplex in self.simplices.iter() {
+                let filtration_value: &T = self.filtration_values.get(simplex).unwrap();
+
+                for face in simplex.get_boundary_array() {
+                    match self.filtration_values.get(&face) {
+                        None => {
+                            return Err(StreamError::MissingFace {
+                                element: simplex.to_string(),
+                                face: face.to_string(),
+                            });
+                        }
+                        Some(face_value) => {
+                            if face_value > filtration_value {
+                                return Err(StreamError::FaceAfterCoface {
+                                    element: simplex.to_string(),
+                                    face: face.to_string(),
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+
+            return Ok(());
+        }
+    }
+
+    impl<T> FilteredStream<Simplex, T> for ExplicitSimplexStream<T>
+    where
+        T: Clone + Debug + PartialOrd,
+    {
+        fn finalize_stream(&mut self) -> Result<(), StreamError> {
+            if self.is_finalized {
+                return Ok(());
+            }
+
+            self.validate()?;
+
+            // Ties in filtration value are broken by dimension, so faces always come first.
+            // Unordered values were rejected when adding the simplices.
+            let filtration_values: &HashMap<Simplex, T> = &self.filtration_values;
+            self.simplices.sort_by(|a, b| {
+                filtration_values
+                    .get(a)
+                    .partial_cmp(&filtration_values.get(b))
+                    .unwrap_or(Equal)
+                    .then_with(|| a.cmp(b))
+            });
+            self.is_finalized = true;
+
+            return Ok(());
+        }
+
+        fn is_finalized(&self) -> bool {
+            return self.is_finalized;
+        }
+
+        fn get_size(&self) -> usize {
+            return self.simplices.len();
+        }
+
+        fn iter(&self) -> std::slice::Iter<'_, Simplex> {
+            return self.simplices.iter();
+        }
+
+        fn get_filtration_value(&self, element: &Simplex) -> Option<T> {
+            return self.filtration_values.get(element).cloned();
+        }
+
+        fn get_dimension(&self, element: &Simplex) -> u32 {
+            return element.get_dimension();
+        }
+
+        fn get_boundary(&self, element: &Simplex) -> Vec<(Simplex, i64)> {
+            return element.get_boundary();
+        }
+    }
+
+    impl<T> Default for ExplicitSimplexStream<T>
+    where
+        T: Clone + Debug + PartialOrd,
+    {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::explicit_simplex_stream::ExplicitSimplexStream;
+    use crate::filtered_stream::filtered_stream::{FilteredStream, StreamError};
+    use crate::simplex::simplex::Simplex;
+
+    #[test]
+    fn test_finalized_filtration_order() {
+        let mut stream: ExplicitSimplexStream<u32> = ExplicitSimplexStream::new();
+        stream.add_element(&[0, 1, 2], 2).unwrap();
+        stream.add_element(&[1, 2], 1).unwrap();
+        stream.add_element(&[0, 2], 2).unwrap();
+        stream.add_element(&[0, 1], 1).unwrap();
+        stream.add_vertex(2, 0).unwrap();
+        stream.add_vertex(1, 0).unwrap();
+        stream.add_vertex(0, 0).unwrap();
+        stream.finalize_stream().unwrap();
+
+        let order: Vec<Simplex> = stream.iter().cloned().collect();
+
+        assert!(stream.is_finalized());
+        assert_eq!(stream.get_size(), 7);
+        assert_eq!(
+            order,
+            vec![
+                Simplex::vertex(0),
+                Simplex::vertex(1),
+                Simplex::vertex(2),
+                Simplex::new(&[0, 1]),
+                Simplex::new(&[1, 2]),
+                Simplex::new(&[0, 2]),
+                Simplex::new(&[0, 1, 2]),
+            ]
+        );
+        assert_eq!(stream.get_filtration_value(&Simplex::new(&[0, 2])), Some(2));
+        assert_eq!(stream.add_vertex(3, 4), Err(StreamError::AlreadyFinalized));
+    }
+
+    #[test]
+    fn test_validation_errors() {
+        let mut missing_face: ExplicitSimplexStream<u32> = ExplicitSimplexStream::new();
+        missing_face.add_vertex(0, 0).unwrap();
+        missing_face.add_element(&[0, 1], 1).unwrap();
+
+        assert!(matches!(
+            missing_face.finalize_stream(),
+            Err(StreamError::MissingFace { .. })
+        ));
+
+        let mut late_face: ExplicitSimplexStream<u32> = ExplicitSimplexStream::new();
+        late_face.add_vertex(0, 0).unwrap();
+        late_face.add_vertex(1, 3).unwrap();
+        late_face.add_element(&[0, 1], 1).unwrap();
+
+        assert!(matches!(
+            late_face.finalize_stream(),
+            Err(StreamError::FaceAfterCoface { .. })
+        ));
+        assert!(matches!(
+            late_face.add_vertex(0, 2),
+            Err(StreamError::DuplicateElement { .. })
+        ));
+
+        let mut nan: ExplicitSimplexStream<f64> = ExplicitSimplexStream::new();
+        assert_eq!(
+            nan.add_vertex(0, f64::NAN),
+            Err(StreamError::UnorderedFiltrationValue {
+                element: Simplex::vertex(0).to_string()
+            })
+        );
+        assert!(!nan.contains(&Simplex::vertex(0)));
+    }
+
+    #[test]
+    fn test_ensure_all_faces() {
+        let mut stream: ExplicitSimplexStream<f64> = ExplicitSimplexStream::new();
+        stream.add_element(&[0, 1, 2, 3], 3.0).unwrap();
+        stream.add_element(&[0, 1], 1.0).unwrap();
+        stream.ensure_all_faces().unwrap();
+        stream.finalize_stream().unwrap();
+
+        assert_eq!(stream.get_size(), 15);
+        assert_eq!(stream.get_filtration_value(&Simplex::vertex(0)), Some(1.0));
+        assert_eq!(stream.get_filtration_value(&Simplex::vertex(3)), Some(3.0));
+        assert_eq!(
+            stream.get_filtration_value(&Simplex::new(&[0, 1])),
+            Some(1.0)
+        );
+    }
+}
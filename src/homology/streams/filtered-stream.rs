@@ -0,0 +1,74 @@
+#![allow(dead_code)]
+pub mod filtered_stream {
+    use std::fmt::{Debug, Display};
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum StreamError {
+        AlreadyFinalized,
+        DuplicateElement { element: String },
+        MissingFace { element: String, face: String },
+        FaceAfterCoface { element: String, face: String },
+        FaceDimensionMismatch { element: String, face: String },
+        NonZeroSquaredBoundary { element: String },
+        UnorderedFiltrationValue { element: String },
+    }
+
+    impl Display for StreamError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                StreamError::AlreadyFinalized => {
+                    write!(f, "The stream has already been finalized")
+                }
+                StreamError::DuplicateElement { element } => {
+                    write!(f, "The element {} has already been added", element)
+                }
+                StreamError::MissingFace { element, face } => {
+                    write!(f, "The face {} of {} is not in the stream", face, element)
+                }
+                StreamError::FaceAfterCoface { element, face } => {
+                    write!(f, "The face {} appears after its coface {}", face, element)
+                }
+                StreamError::FaceDimensionMismatch { element, face } => {
+                    write!(
+                        f,
+                        "The face {} of {} has the wrong dimension",
+                        face, element
+                    )
+                }
+                StreamError::NonZeroSquaredBoundary { element } => {
+                    write!(f, "The boundary of the boundary of {} is not zero", element)
+                }
+                StreamError::UnorderedFiltrationValue { element } => {
+                    write!(
+                        f,
+                        "The filtration value of {} is not comparable to itself",
+                        element
+                    )
+                }
+            }
+        }
+    }
+
+    impl std::error::Error for StreamError {}
+
+    // Analogue of JavaPlex's AbstractFilteredStream. A stream is a collection of basis
+    // elements B (simplices, cells, ...) with filtration values of type T. Once finalized,
+    // iteration visits the elements in filtration order, with every face appearing before
+    // its cofaces.
+    pub trait FilteredStream<B, T> {
+        fn finalize_stream(&mut self) -> Result<(), StreamError>;
+
+        fn is_finalized(&self) -> bool;
+
+        fn get_size(&self) -> usize;
+
+        fn iter(&self) -> std::slice::Iter<'_, B>;
+
+        fn get_filtration_value(&self, element: &B) -> Option<T>;
+
+        fn get_dimension(&self, element: &B) -> u32;
+
+        // Faces of the element paired with their (integer) boundary coefficients
+        fn get_boundary(&self, element: &B) -> Vec<(B, i64)>;
+    }
+}
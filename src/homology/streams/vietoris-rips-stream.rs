@@ -0,0 +1,222 @@
+#![allow(dead_code)]
+pub mod vietoris_rips_stream {
+    use std::collections::HashMap;
+
+    use crate::explicit_simplex_stream::explicit_simplex_stream::ExplicitSimplexStream;
+    use crate::filtered_stream::filtered_stream::{FilteredStream, StreamError};
+    use crate::metric_spaces::metric_spaces::MetricSpace;
+    use crate::simplex::simplex::Simplex;
+
+    // The Vietoris-Rips filtration of a finite metric space, analogue of JavaPlex's
+    // VietorisRipsStream. A simplex enters at the length of its longest edge; only
+    // simplices up to max_dimension and filtration values up to max_filtration_value
+    // are constructed.
+    pub struct VietorisRipsStream {
+        num_points: usize,
+        edge_lengths: HashMap<(u32, u32), f64>,
+        lower_neighbors: Vec<Vec<u32>>,
+        max_dimension: u32,
+        stream: ExplicitSimplexStream<f64>,
+    }
+
+    impl VietorisRipsStream {
+        pub fn new<M>(metric_space: &M, max_filtration_value: f64, max_dimension: u32) -> Self
+        where
+            M: MetricSpace,
+        {
+            let num_points: usize = metric_space.get_size();
+            let mut edge_lengths: HashMap<(u32, u32), f64> = HashMap::new();
+            let mut lower_neighbors: Vec<Vec<u32>> = vec![Vec::new(); num_points];
+
+            for (j, neighbors) in lower_neighbors.iter_mut().enumerate() {
+                for i in 0..j {
+                    let length: f64 = metric_space.distance(i, j);
+
+                    if length <= max_filtration_value {
+                        edge_lengths.insert((i as u32, j as u32), length);
+                        neighbors.push(i as u32);
+                    }
+                }
+            }
+
+            Self {
+                num_points,
+                edge_lengths,
+                lower_neighbors,
+                max_dimension,
+                stream: ExplicitSimplexStream::new(),
+            }
+        }
+
+        pub fn get_num_points(&self) -> usize {
+            return self.num_points;
+        }
+
+        // Incremental expansion of the neighborhood graph (Zomorodian), every simplex is
+        // grown by adding a common lower neighbor of all of its vertices.
+        fn construct_simplices(&mut self) -> Result<(), StreamError> {
+            for v in 0..self.num_points as u32 {
+                let vertex: Simplex = Simplex::vertex(v);
+                let candidates: Vec<u32> = self.lower_neighbors[v as usize].clone();
+
+                self.stream.add_simplex(vertex.clone(), 0.0)?;
+                self.add_cofaces(&vertex, 0.0, &candidates)?;
+            }
+
+            return Ok(());
+        }
+
+        fn add_cofaces(
+            &mut self,
+            simplex: &Simplex,
+            filtration_value: f64,
+            candidates: &[u32],
+        ) -> Result<(), StreamError> {
+            if simplex.get_dimension() >= self.max_dimension {
+                return Ok(());
+            }
+
+            for v in candidates.iter() {
+                let coface_value: f64 = simplex
+                    .get_vertices()
+                    .iter()
+                    .map(|u| self.edge_lengths[&(*v, *u)])
+                    .fold(filtration_value, f64::max);
+                let mut coface_vertices: Vec<u32> = simplex.get_vertices().to_vec();
+                coface_vertices.push(*v);
+                let coface: Simplex = Simplex::new(&coface_vertices);
+
+                let coface_candidates: Vec<u32> = candidates
+                    .iter()
+                    .filter(|u| **u < *v && self.edge_lengths.contains_key(&(**u, *v)))
+                    .cloned()
+                    .collect();
+
+                self.stream.add_simplex(coface.clone(), coface_value)?;
+                self.add_cofaces(&coface, coface_value, &coface_candidates)?;
+            }
+
+            return Ok(());
+        }
+    }
+
+    impl FilteredStream<Simplex, f64> for VietorisRipsStream {
+        fn finalize_stream(&mut self) -> Result<(), StreamError> {
+            if self.stream.is_finalized() {
+                return Ok(());
+            }
+
+            self.construct_simplices()?;
+
+            return self.stream.finalize_stream();
+        }
+
+        fn is_finalized(&self) -> bool {
+            return self.stream.is_finalized();
+        }
+
+        fn get_size(&self) -> usize {
+            return self.stream.get_size();
+        }
+
+        fn iter(&self) -> std::slice::Iter<'_, Simplex> {
+            return self.stream.iter();
+        }
+
+        fn get_filtration_value(&self, element: &Simplex) -> Option<f64> {
+            return self.stream.get_filtration_value(element);
+        }
+
+        fn get_dimension(&self, element: &Simplex) -> u32 {
+            return element.get_dimension();
+        }
+
+        fn get_boundary(&self, element: &Simplex) -> Vec<(Simplex, i64)> {
+            return element.get_boundary();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::vietoris_rips_stream::VietorisRipsStream;
+    use crate::absolute_homology::absolute_homology::AbsoluteHomology;
+    use crate::barcode_collections::barcode_collections::BarcodeCollection;
+    use crate::fields::fields::BooleanField;
+    use crate::filtered_stream::filtered_stream::FilteredStream;
+    use crate::intervals::intervals::Interval;
+    use crate::metric_spaces::metric_spaces::{EuclideanMetricSpace, ExplicitMetricSpace};
+    use crate::simplex::simplex::Simplex;
+
+    fn unit_square() -> EuclideanMetricSpace {
+        return EuclideanMetricSpace::new(vec![
+            vec![0.0, 0.0],
+            vec![1.0, 0.0],
+            vec![1.0, 1.0],
+            vec![0.0, 1.0],
+        ]);
+    }
+
+    #[test]
+    fn test_rips_filtration_values() {
+        let mut stream: VietorisRipsStream = VietorisRipsStream::new(&unit_square(), 1.5, 2);
+        stream.finalize_stream().unwrap();
+
+        // 4 vertices, 6 edges and 4 triangles
+        assert_eq!(stream.get_size(), 14);
+        assert_eq!(
+            stream.get_filtration_value(&Simplex::new(&[0, 1])),
+            Some(1.0)
+        );
+        assert_eq!(
+            stream.get_filtration_value(&Simplex::new(&[0, 1, 2])),
+            Some(2.0_f64.sqrt())
+        );
+
+        let mut truncated: VietorisRipsStream = VietorisRipsStream::new(&unit_square(), 1.0, 2);
+        truncated.finalize_stream().unwrap();
+
+        assert_eq!(truncated.get_size(), 8);
+        assert_eq!(truncated.get_filtration_value(&Simplex::new(&[0, 2])), None);
+    }
+
+    #[test]
+    fn test_rips_barcodes_of_square() {
+        let mut stream: VietorisRipsStream = VietorisRipsStream::new(&unit_square(), 2.0, 2);
+        stream.finalize_stream().unwrap();
+
+        let barcodes: BarcodeCollection<f64, ()> =
+            AbsoluteHomology::new(BooleanField, 2).compute_intervals(&stream);
+        let dimension_zero: Vec<Interval<f64>> = barcodes.get_intervals_at_dimension(0);
+
+        assert_eq!(dimension_zero.len(), 4);
+        assert_eq!(dimension_zero.iter().filter(|i| i.is_infinite()).count(), 1);
+        assert_eq!(
+            barcodes.get_intervals_at_dimension(1),
+            vec![Interval::finite_right_open_interval(1.0, 2.0_f64.sqrt())]
+        );
+    }
+
+    #[test]
+    fn test_rips_from_distance_matrix() {
+        // Four points on a cycle graph with the path metric
+        let distances: ExplicitMetricSpace = ExplicitMetricSpace::new(vec![
+            vec![0.0, 1.0, 2.0, 1.0],
+            vec![1.0, 0.0, 1.0, 2.0],
+            vec![2.0, 1.0, 0.0, 1.0],
+            vec![1.0, 2.0, 1.0, 0.0],
+        ]);
+        let mut stream: VietorisRipsStream = VietorisRipsStream::new(&distances, 3.0, 3);
+        stream.finalize_stream().unwrap();
+
+        let barcodes: BarcodeCollection<f64, ()> =
+            AbsoluteHomology::new(BooleanField, 3).compute_intervals(&stream);
+
+        assert_eq!(stream.get_size(), 15);
+        assert_eq!(
+            barcodes.get_intervals_at_dimension(1),
+            vec![Interval::finite_right_open_interval(1.0, 2.0)]
+        );
+        assert!(barcodes.get_intervals_at_dimension(2).is_empty());
+    }
+}
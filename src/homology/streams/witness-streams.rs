@@ -0,0 +1,379 @@
+#![allow(dead_code)]
+pub mod witness_streams {
+    use std::cmp::Ordering;
+
+    use crate::explicit_simplex_stream::explicit_simplex_stream::ExplicitSimplexStream;
+    use crate::filtered_stream::filtered_stream::{FilteredStream, StreamError};
+    use crate::metric_spaces::metric_spaces::{ExplicitMetricSpace, MetricSpace};
+    use crate::simplex::simplex::Simplex;
+    use crate::vietoris_rips_stream::vietoris_rips_stream::VietorisRipsStream;
+
+    // Distances from every data point (witness) to every landmark, together with the
+    // offsets m_w given by the distance from w to its nu-th closest landmark (m_w = 0 for
+    // nu = 0). Vertex i of the witness complexes is the i-th landmark.
+    struct WitnessDistances {
+        distances: Vec<Vec<f64>>,
+        offsets: Vec<f64>,
+    }
+
+    impl WitnessDistances {
+        fn new<M>(metric_space: &M, landmarks: &[usize], nu: usize) -> Self
+        where
+            M: MetricSpace,
+        {
+            assert!(
+                nu <= landmarks.len(),
+                "nu must not exceed the number of landmarks"
+            );
+
+            let (distances, offsets): (Vec<Vec<f64>>, Vec<f64>) = (0..metric_space.get_size())
+                .map(|w| Self::witness_row(metric_space, landmarks, nu, w))
+                .unzip();
+
+            Self { distances, offsets }
+        }
+
+        // Distances from the witness w to the landmarks, with its offset m_w
+        fn witness_row<M>(
+            metric_space: &M,
+            landmarks: &[usize],
+            nu: usize,
+            w: usize,
+        ) -> (Vec<f64>, f64)
+        where
+            M: MetricSpace,
+        {
+            let row: Vec<f64> = landmarks
+                .iter()
+                .map(|l| metric_space.distance(w, *l))
+                .collect();
+
+            if nu == 0 {
+                return (row, 0.0);
+            }
+
+            let mut sorted_row: Vec<f64> = row.clone();
+            sorted_row.sort_by(f64::total_cmp);
+            let offset: f64 = sorted_row[nu - 1];
+
+            return (row, offset);
+        }
+
+        // Earliest time at which some witness sees all of the given landmarks
+        fn witness_value(&self, landmarks: &[u32]) -> f64 {
+            return self
+                .distances
+                .iter()
+                .zip(self.offsets.iter())
+                .map(|(row, offset)| {
+                    landmarks
+                        .iter()
+                        .map(|l| row[*l as usize])
+                        .fold(f64::NEG_INFINITY, f64::max)
+                        - offset
+                })
+                .fold(f64::INFINITY, f64::min)
+                .max(0.0);
+        }
+
+        fn num_landmarks(&self) -> usize {
+            return self.distances.first().map_or(0, |row| row.len());
+        }
+    }
+
+    // Lazy witness complex, analogue of JavaPlex's LazyWitnessStream. The edge between two
+    // landmarks enters at min_w max(d(a, w), d(b, w)) - m_w, and higher simplices enter as
+    // soon as all of their edges are present, so this is the flag complex of the edges.
+    pub struct LazyWitnessStream {
+        stream: VietorisRipsStream,
+    }
+
+    impl LazyWitnessStream {
+        pub fn new<M>(
+            metric_space: &M,
+            landmarks: &[usize],
+            nu: usize,
+            max_filtration_value: f64,
+            max_dimension: u32,
+        ) -> Self
+        where
+            M: MetricSpace,
+        {
+            assert!(
+                nu <= landmarks.len(),
+                "nu must not exceed the number of landmarks"
+            );
+
+            // One pass over the witnesses, keeping the running minimum of every edge {a, b}
+            // with a < b in minima[a][b - a - 1]. The rows are not stored, since only edges
+            // are needed.
+            let num_landmarks: usize = landmarks.len();
+            let mut minima: Vec<Vec<f64>> = (0..num_landmarks)
+                .map(|a| vec![f64::INFINITY; num_landmarks - a - 1])
+                .collect();
+
+            for w in 0..metric_space.get_size() {
+                let (row, offset): (Vec<f64>, f64) =
+                    WitnessDistances::witness_row(metric_space, landmarks, nu, w);
+
+                for (a, edges) in minima.iter_mut().enumerate() {
+                    for (minimum, distance) in edges.iter_mut().zip(row[(a + 1)..].iter()) {
+                        *minimum = minimum.min(row[a].max(*distance) - offset);
+                    }
+                }
+            }
+
+            // Mirror the upper triangle into the symmetric matrix
+            let edge_values: Vec<Vec<f64>> = (0..num_landmarks)
+                .map(|a| {
+                    (0..num_landmarks)
+                        .map(|b| match a.cmp(&b) {
+                            Ordering::Less => minima[a][b - a - 1].max(0.0),
+                            Ordering::Equal => 0.0,
+                            Ordering::Greater => minima[b][a - b - 1].max(0.0),
+                        })
+                        .collect()
+                })
+                .collect();
+
+            Self {
+                stream: VietorisRipsStream::new(
+                    &ExplicitMetricSpace::new(edge_values),
+                    max_filtration_value,
+                    max_dimension,
+                ),
+            }
+        }
+    }
+
+    impl FilteredStream<Simplex, f64> for LazyWitnessStream {
+        fn finalize_stream(&mut self) -> Result<(), StreamError> {
+            return self.stream.finalize_stream();
+        }
+
+        fn is_finalized(&self) -> bool {
+            return self.stream.is_finalized();
+        }
+
+        fn get_size(&self) -> usize {
+            return self.stream.get_size();
+        }
+
+        fn iter(&self) -> std::slice::Iter<'_, Simplex> {
+            return self.stream.iter();
+        }
+
+        fn get_filtration_value(&self, element: &Simplex) -> Option<f64> {
+            return self.stream.get_filtration_value(element);
+        }
+
+        fn get_dimension(&self, element: &Simplex) -> u32 {
+            return element.get_dimension();
+        }
+
+        fn get_boundary(&self, element: &Simplex) -> Vec<(Simplex, i64)> {
+            return element.get_boundary();
+        }
+    }
+
+    // Witness complex, analogue of JavaPlex's WitnessStream. A simplex of landmarks enters
+    // once a single witness sees all of its vertices, i.e. at min_w max_i d(l_i, w) - m_w.
+    pub struct WitnessStream {
+        witness_distances: WitnessDistances,
+        max_filtration_value: f64,
+        max_dimension: u32,
+        stream: ExplicitSimplexStream<f64>,
+    }
+
+    impl WitnessStream {
+        pub fn new<M>(
+            metric_space: &M,
+            landmarks: &[usize],
+            nu: usize,
+            max_filtration_value: f64,
+            max_dimension: u32,
+        ) -> Self
+        where
+            M: MetricSpace,
+        {
+            Self {
+                witness_distances: WitnessDistances::new(metric_space, landmarks, nu),
+                max_filtration_value,
+                max_dimension,
+                stream: ExplicitSimplexStream::new(),
+            }
+        }
+
+        // The witness value can only grow when adding vertices, so a simplex above the
+        // maximum filtration value has no cofaces below it and the search is pruned there.
+        fn construct_simplices(&mut self) -> Result<(), StreamError> {
+            for v in 0..self.witness_distances.num_landmarks() as u32 {
+                self.add_simplex_and_cofaces(vec![v])?;
+            }
+
+            return Ok(());
+        }
+
+        fn add_simplex_and_cofaces(&mut self, vertices: Vec<u32>) -> Result<(), StreamError> {
+            let filtration_value: f64 = self.witness_distances.witness_value(&vertices);
+
+            if filtration_value > self.max_filtration_value {
+                return Ok(());
+            }
+
+            self.stream
+                .add_simplex(Simplex::new(&vertices), filtration_value)?;
+
+            if vertices.len() as u32 > self.max_dimension {
+                return Ok(());
+            }
+
+            for v in 0..*vertices.last().unwrap() {
+                let mut coface_vertices: Vec<u32> = vertices.clone();
+                coface_vertices.push(v);
+                self.add_simplex_and_cofaces(coface_vertices)?;
+            }
+
+            return Ok(());
+        }
+    }
+
+    impl FilteredStream<Simplex, f64> for WitnessStream {
+        fn finalize_stream(&mut self) -> Result<(), StreamError> {
+            if self.stream.is_finalized() {
+                return Ok(());
+            }
+
+            self.construct_simplices()?;
+
+            return self.stream.finalize_stream();
+        }
+
+        fn is_finalized(&self) -> bool {
+            return self.stream.is_finalized();
+        }
+
+        fn get_size(&self) -> usize {
+            return self.stream.get_size();
+        }
+
+        fn iter(&self) -> std::slice::Iter<'_, Simplex> {
+            return self.stream.iter();
+        }
+
+        fn get_filtration_value(&self, element: &Simplex) -> Option<f64> {
+            return self.stream.get_filtration_value(element);
+        }
+
+        fn get_dimension(&self, element: &Simplex) -> u32 {
+            return element.get_dimension();
+        }
+
+        fn get_boundary(&self, element: &Simplex) -> Vec<(Simplex, i64)> {
+            return element.get_boundary();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::witness_streams::{LazyWitnessStream, WitnessStream};
+    use crate::absolute_homology::absolute_homology::AbsoluteHomology;
+    use crate::barcode_collections::barcode_collections::AnnotatedBarcodeCollection;
+    use crate::chain::chain::Chain;
+    use crate::fields::fields::BooleanField;
+    use crate::filtered_stream::filtered_stream::FilteredStream;
+    use crate::intervals::intervals::Interval;
+    use crate::metric_spaces::metric_spaces::EuclideanMetricSpace;
+    use crate::simplex::simplex::Simplex;
+    use std::f64::consts::PI;
+
+    fn circle(num_points: usize) -> EuclideanMetricSpace {
+        return EuclideanMetricSpace::new(
+            (0..num_points)
+                .map(|i| {
+                    let angle: f64 = 2.0 * PI * i as f64 / num_points as f64;
+                    vec![angle.cos(), angle.sin()]
+                })
+                .collect(),
+        );
+    }
+
+    #[test]
+    fn test_witness_filtration_values() {
+        let points: EuclideanMetricSpace =
+            EuclideanMetricSpace::new(vec![vec![0.0], vec![1.0], vec![2.0]]);
+
+        let mut nu_zero: WitnessStream = WitnessStream::new(&points, &[0, 2], 0, 5.0, 1);
+        let mut nu_one: LazyWitnessStream = LazyWitnessStream::new(&points, &[0, 2], 1, 5.0, 1);
+        nu_zero.finalize_stream().unwrap();
+        nu_one.finalize_stream().unwrap();
+
+        // The middle point witnesses the edge at distance 1, or immediately once it is
+        // offset by the distance to its closest landmark
+        assert_eq!(
+            nu_zero.get_filtration_value(&Simplex::new(&[0, 1])),
+            Some(1.0)
+        );
+        assert_eq!(
+            nu_one.get_filtration_value(&Simplex::new(&[0, 1])),
+            Some(0.0)
+        );
+        assert_eq!(nu_zero.get_filtration_value(&Simplex::vertex(1)), Some(0.0));
+    }
+
+    #[test]
+    fn test_strict_witness_values_dominate_lazy_values() {
+        let points: EuclideanMetricSpace = circle(24);
+        let landmarks: Vec<usize> = (0..24).step_by(4).collect();
+
+        let mut lazy: LazyWitnessStream = LazyWitnessStream::new(&points, &landmarks, 1, 2.0, 2);
+        let mut strict: WitnessStream = WitnessStream::new(&points, &landmarks, 1, 2.0, 2);
+        lazy.finalize_stream().unwrap();
+        strict.finalize_stream().unwrap();
+
+        for simplex in strict.iter() {
+            assert!(
+                strict.get_filtration_value(simplex).unwrap()
+                    >= lazy.get_filtration_value(simplex).unwrap()
+            );
+
+            // Both complexes agree on vertices and edges
+            if simplex.get_dimension() <= 1 {
+                assert_eq!(
+                    strict.get_filtration_value(simplex),
+                    lazy.get_filtration_value(simplex)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_lazy_witness_barcodes_of_circle() {
+        let points: EuclideanMetricSpace = circle(60);
+        let landmarks: Vec<usize> = (0..60).step_by(6).collect();
+
+        let mut stream: LazyWitnessStream = LazyWitnessStream::new(&points, &landmarks, 0, 2.0, 2);
+        stream.finalize_stream().unwrap();
+
+        let barcodes: AnnotatedBarcodeCollection<f64, Chain<Simplex, bool>> =
+            AbsoluteHomology::new(BooleanField, 2).compute_annotated_intervals(&stream);
+        let dimension_one: Vec<Interval<f64>> = barcodes.get_intervals_at_dimension(1);
+
+        assert_eq!(
+            barcodes
+                .get_intervals_at_dimension(0)
+                .iter()
+                .filter(|i| i.is_infinite())
+                .count(),
+            1
+        );
+        assert_eq!(dimension_one.len(), 1);
+        assert!(!dimension_one[0].contains_point(0.1));
+        assert!(dimension_one[0].contains_point(0.5));
+
+        // The generating cycle passes through every landmark
+        let (_, cycle) = &barcodes.get_interval_generator_pairs_at_dimension(1)[0];
+        assert_eq!(cycle.len(), 10);
+    }
+}
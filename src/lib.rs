@@ -1,3 +1,5 @@
+#![allow(clippy::needless_return, clippy::module_inception)]
+
 #[path = "homology/barcodes/intervals.rs"]
 mod intervals;
 
@@ -6,3 +8,81 @@ mod persistence_invariant_descriptor;
 
 #[path = "homology/barcodes/barcode-collections.rs"]
 mod barcode_collections;
+
+#[path = "homology/barcodes/bottleneck-distance.rs"]
+mod bottleneck_distance;
+
+#[path = "homology/barcodes/wasserstein-distance.rs"]
+mod wasserstein_distance;
+
+#[path = "homology/barcodes/persistence-landscape.rs"]
+mod persistence_landscape;
+
+#[path = "homology/barcodes/persistence-image.rs"]
+mod persistence_image;
+
+#[path = "homology/barcodes/betti-curves.rs"]
+mod betti_curves;
+
+#[path = "homology/barcodes/barcode-io.rs"]
+mod barcode_io;
+
+#[path = "algebraic/fields.rs"]
+mod fields;
+
+#[path = "homology/chain-basis/simplex.rs"]
+mod simplex;
+
+#[path = "homology/chain-basis/cube.rs"]
+mod cube;
+
+#[path = "homology/chain-basis/cell.rs"]
+mod cell;
+
+#[path = "homology/chain-basis/chain.rs"]
+mod chain;
+
+#[path = "homology/streams/filtered-stream.rs"]
+mod filtered_stream;
+
+#[path = "homology/streams/explicit-simplex-stream.rs"]
+mod explicit_simplex_stream;
+
+#[path = "homology/streams/explicit-cell-stream.rs"]
+mod explicit_cell_stream;
+
+#[path = "homology/streams/vietoris-rips-stream.rs"]
+mod vietoris_rips_stream;
+
+#[path = "homology/streams/witness-streams.rs"]
+mod witness_streams;
+
+#[path = "homology/streams/cubical-complex-stream.rs"]
+mod cubical_complex_stream;
+
+#[path = "homology/persistence/absolute-homology.rs"]
+mod absolute_homology;
+
+#[path = "homology/persistence/absolute-cohomology.rs"]
+mod absolute_cohomology;
+
+#[path = "homology/persistence/relative-homology.rs"]
+mod relative_homology;
+
+#[path = "homology/persistence/zigzag-persistence.rs"]
+mod zigzag_persistence;
+
+#[path = "metric/metric-spaces.rs"]
+mod metric_spaces;
+
+#[path = "metric/landmark-selectors.rs"]
+mod landmark_selectors;
+
+#[path = "visualization/svg-document.rs"]
+mod svg_document;
+
+#[path = "visualization/barcode-visualizer.rs"]
+mod barcode_visualizer;
+
+#[path = "visualization/persistence-diagram-visualizer.rs"]
+mod persistence_diagram_visualizer;
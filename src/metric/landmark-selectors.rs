@@ -0,0 +1,278 @@
+#![allow(dead_code)]
+pub mod landmark_selectors {
+    use crate::metric_spaces::metric_spaces::MetricSpace;
+
+    // Landmark selection over a finite metric space, as used to build witness complexes.
+    // Landmarks are reported as indices into the underlying point set.
+    pub trait LandmarkSelector {
+        fn get_landmark_points(&self) -> &[usize];
+
+        // Covering radius of the landmark set, the largest distance from a point to its
+        // closest landmark
+        fn get_max_distance_from_points_to_landmarks(&self) -> f64;
+    }
+
+    fn covering_radius<M>(metric_space: &M, landmarks: &[usize]) -> f64
+    where
+        M: MetricSpace,
+    {
+        return (0..metric_space.get_size())
+            .map(|i| {
+                landmarks
+                    .iter()
+                    .map(|l| metric_space.distance(i, *l))
+                    .fold(f64::INFINITY, f64::min)
+            })
+            .fold(0.0, f64::max);
+    }
+
+    // Sequential max-min (farthest point) selection, analogue of JavaPlex's
+    // MaxMinLandmarkSelector. Starting from the seed point, every new landmark is the point
+    // farthest away from the landmarks chosen so far.
+    pub struct MaxMinLandmarkSelector {
+        landmarks: Vec<usize>,
+        max_distance: f64,
+    }
+
+    impl MaxMinLandmarkSelector {
+        pub fn new<M>(metric_space: &M, num_landmarks: usize, seed_point: usize) -> Self
+        where
+            M: MetricSpace,
+        {
+            let num_points: usize = metric_space.get_size();
+
+            assert!(
+                num_landmarks >= 1 && num_landmarks <= num_points,
+                "The number of landmarks must be between 1 and the number of points"
+            );
+            assert!(
+                seed_point < num_points,
+                "The seed point is not in the point set"
+            );
+
+            let mut landmarks: Vec<usize> = vec![seed_point];
+            let mut is_chosen: Vec<bool> = vec![false; num_points];
+            is_chosen[seed_point] = true;
+            let mut distances_to_landmarks: Vec<f64> = (0..num_points)
+                .map(|i| metric_space.distance(i, seed_point))
+                .collect();
+
+            while landmarks.len() < num_landmarks {
+                // Ties are broken by the lowest index so the selection is deterministic. Points
+                // already chosen are skipped, since with coincident points every remaining
+                // distance can be zero.
+                let (farthest_point, _) = distances_to_landmarks
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| !is_chosen[*i])
+                    .fold(
+                        (None, f64::NEG_INFINITY),
+                        |(best, best_distance), (i, d)| {
+                            if *d > best_distance {
+                                (Some(i), *d)
+                            } else {
+                                (best, best_distance)
+                            }
+                        },
+                    );
+                // NaN distances never win, so fall back to the lowest unchosen point
+                let farthest_point: usize = farthest_point
+                    .unwrap_or_else(|| (0..num_points).find(|i| !is_chosen[*i]).unwrap());
+
+                landmarks.push(farthest_point);
+                is_chosen[farthest_point] = true;
+
+                for (i, d) in distances_to_landmarks.iter_mut().enumerate() {
+                    *d = d.min(metric_space.distance(i, farthest_point));
+                }
+            }
+
+            Self {
+                landmarks,
+                max_distance: distances_to_landmarks.into_iter().fold(0.0, f64::max),
+            }
+        }
+    }
+
+    impl LandmarkSelector for MaxMinLandmarkSelector {
+        fn get_landmark_points(&self) -> &[usize] {
+            return &self.landmarks;
+        }
+
+        fn get_max_distance_from_points_to_landmarks(&self) -> f64 {
+            return self.max_distance;
+        }
+    }
+
+    // Uniform random selection without replacement, analogue of JavaPlex's
+    // RandomLandmarkSelector. The same seed always produces the same landmarks.
+    pub struct RandomLandmarkSelector {
+        landmarks: Vec<usize>,
+        max_distance: f64,
+    }
+
+    impl RandomLandmarkSelector {
+        pub fn new<M>(metric_space: &M, num_landmarks: usize, seed: u64) -> Self
+        where
+            M: MetricSpace,
+        {
+            let num_points: usize = metric_space.get_size();
+
+            assert!(
+                num_landmarks >= 1 && num_landmarks <= num_points,
+                "The number of landmarks must be between 1 and the number of points"
+            );
+
+            // Partial Fisher-Yates shuffle of the point indices
+            let mut generator: SplitMix64 = SplitMix64::new(seed);
+            let mut indices: Vec<usize> = (0..num_points).collect();
+
+            for i in 0..num_landmarks {
+                let j: usize = i + generator.next_below((num_points - i) as u64) as usize;
+                indices.swap(i, j);
+            }
+
+            indices.truncate(num_landmarks);
+
+            Self {
+                max_distance: covering_radius(metric_space, &indices),
+                landmarks: indices,
+            }
+        }
+    }
+
+    impl LandmarkSelector for RandomLandmarkSelector {
+        fn get_landmark_points(&self) -> &[usize] {
+            return &self.landmarks;
+        }
+
+        fn get_max_distance_from_points_to_landmarks(&self) -> f64 {
+            return self.max_distance;
+        }
+    }
+
+    // Small seedable generator (Steele, Lea and Flood's SplitMix64), so that we do not need
+    // to pull in a dependency for reproducible sampling
+    struct SplitMix64 {
+        state: u64,
+    }
+
+    impl SplitMix64 {
+        fn new(seed: u64) -> Self {
+            Self { state: seed }
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+
+            let mut z: u64 = self.state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+
+            return z ^ (z >> 31);
+        }
+
+        // Uniform value in [0, bound), rejecting the biased tail of the u64 range
+        fn next_below(&mut self, bound: u64) -> u64 {
+            let zone: u64 = u64::MAX - u64::MAX % bound;
+
+            loop {
+                let value: u64 = self.next_u64();
+
+                if value < zone {
+                    return value % bound;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::landmark_selectors::{
+        LandmarkSelector, MaxMinLandmarkSelector, RandomLandmarkSelector,
+    };
+    use crate::metric_spaces::metric_spaces::{
+        EuclideanMetricSpace, ExplicitMetricSpace, MetricSpace,
+    };
+
+    fn line_points() -> EuclideanMetricSpace {
+        return EuclideanMetricSpace::new((0..11).map(|i| vec![i as f64]).collect());
+    }
+
+    #[test]
+    fn test_max_min_selection() {
+        let selector: MaxMinLandmarkSelector = MaxMinLandmarkSelector::new(&line_points(), 3, 0);
+
+        assert_eq!(selector.get_landmark_points(), &[0, 10, 5]);
+        assert_eq!(selector.get_max_distance_from_points_to_landmarks(), 2.0);
+
+        let seeded: MaxMinLandmarkSelector = MaxMinLandmarkSelector::new(&line_points(), 2, 3);
+        assert_eq!(seeded.get_landmark_points(), &[3, 10]);
+    }
+
+    #[test]
+    fn test_max_min_selection_on_explicit_metric() {
+        let distances: ExplicitMetricSpace = ExplicitMetricSpace::new(vec![
+            vec![0.0, 1.0, 4.0, 2.0],
+            vec![1.0, 0.0, 3.0, 2.0],
+            vec![4.0, 3.0, 0.0, 3.0],
+            vec![2.0, 2.0, 3.0, 0.0],
+        ]);
+        let selector: MaxMinLandmarkSelector = MaxMinLandmarkSelector::new(&distances, 3, 1);
+
+        assert_eq!(selector.get_landmark_points(), &[1, 2, 3]);
+        assert_eq!(selector.get_max_distance_from_points_to_landmarks(), 1.0);
+    }
+
+    // Three points whose distances are all undefined
+    struct UndefinedDistances;
+
+    impl MetricSpace for UndefinedDistances {
+        fn get_size(&self) -> usize {
+            return 3;
+        }
+
+        fn distance(&self, i: usize, j: usize) -> f64 {
+            return if i == j { 0.0 } else { f64::NAN };
+        }
+    }
+
+    #[test]
+    fn test_max_min_selection_with_coincident_points() {
+        let points: EuclideanMetricSpace = EuclideanMetricSpace::new(vec![
+            vec![0.0, 0.0],
+            vec![0.0, 0.0],
+            vec![1.0, 0.0],
+            vec![1.0, 0.0],
+        ]);
+        let coincident: EuclideanMetricSpace = EuclideanMetricSpace::new(vec![vec![2.0]; 3]);
+
+        // Once the distinct points are used up, every remaining distance is zero
+        let selector: MaxMinLandmarkSelector = MaxMinLandmarkSelector::new(&points, 4, 0);
+        let all_equal: MaxMinLandmarkSelector = MaxMinLandmarkSelector::new(&coincident, 2, 0);
+        let all_nan: MaxMinLandmarkSelector =
+            MaxMinLandmarkSelector::new(&UndefinedDistances, 3, 1);
+
+        assert_eq!(selector.get_landmark_points(), &[0, 2, 1, 3]);
+        assert_eq!(all_equal.get_landmark_points(), &[0, 1]);
+        assert_eq!(all_equal.get_max_distance_from_points_to_landmarks(), 0.0);
+        assert_eq!(all_nan.get_landmark_points(), &[1, 0, 2]);
+    }
+
+    #[test]
+    fn test_random_selection_is_reproducible() {
+        let first: RandomLandmarkSelector = RandomLandmarkSelector::new(&line_points(), 5, 42);
+        let second: RandomLandmarkSelector = RandomLandmarkSelector::new(&line_points(), 5, 42);
+        let all: RandomLandmarkSelector = RandomLandmarkSelector::new(&line_points(), 11, 7);
+
+        let mut distinct: Vec<usize> = first.get_landmark_points().to_vec();
+        distinct.sort_unstable();
+        distinct.dedup();
+
+        assert_eq!(first.get_landmark_points(), second.get_landmark_points());
+        assert_eq!(distinct.len(), 5);
+        assert!(distinct.iter().all(|i| *i < 11));
+        assert_eq!(all.get_max_distance_from_points_to_landmarks(), 0.0);
+    }
+}
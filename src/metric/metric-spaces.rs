@@ -0,0 +1,244 @@
+#![allow(dead_code)]
+pub mod metric_spaces {
+    use std::cmp::Ordering;
+
+    // A finite metric space whose points are addressed by index, analogue of JavaPlex's
+    // AbstractSearchableMetricSpace. The neighbour searches are brute force by default.
+    pub trait MetricSpace {
+        fn get_size(&self) -> usize;
+
+        fn distance(&self, i: usize, j: usize) -> f64;
+
+        // The k points closest to the query point, nearest first and excluding the query
+        // point itself. Ties are broken by index.
+        fn get_k_nearest_neighbors(&self, query_index: usize, k: usize) -> Vec<usize> {
+            let mut neighbors: Vec<(f64, usize)> = (0..self.get_size())
+                .filter(|i| *i != query_index)
+                .map(|i| (self.distance(query_index, i), i))
+                .collect();
+            neighbors.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+
+            return neighbors.into_iter().take(k).map(|(_, i)| i).collect();
+        }
+
+        // Every point other than the query point at distance at most radius, in index order
+        fn get_neighbors_within_radius(&self, query_index: usize, radius: f64) -> Vec<usize> {
+            return (0..self.get_size())
+                .filter(|i| *i != query_index && self.distance(query_index, *i) <= radius)
+                .collect();
+        }
+    }
+
+    fn validate_points(points: &[Vec<f64>]) {
+        if let Some(first) = points.first() {
+            assert!(
+                points.iter().all(|p| p.len() == first.len()),
+                "All points must have the same dimension"
+            );
+        }
+    }
+
+    // Points of R^n with the L2 distance
+    #[derive(Debug, Clone)]
+    pub struct EuclideanMetricSpace {
+        points: Vec<Vec<f64>>,
+    }
+
+    impl EuclideanMetricSpace {
+        pub fn new(points: Vec<Vec<f64>>) -> Self {
+            validate_points(&points);
+
+            Self { points }
+        }
+
+        pub fn get_point(&self, index: usize) -> &[f64] {
+            return &self.points[index];
+        }
+    }
+
+    impl MetricSpace for EuclideanMetricSpace {
+        fn get_size(&self) -> usize {
+            return self.points.len();
+        }
+
+        fn distance(&self, i: usize, j: usize) -> f64 {
+            return self.points[i]
+                .iter()
+                .zip(self.points[j].iter())
+                .map(|(a, b)| (a - b) * (a - b))
+                .sum::<f64>()
+                .sqrt();
+        }
+    }
+
+    // Points of R^n with the L1 (taxicab) distance
+    #[derive(Debug, Clone)]
+    pub struct ManhattanMetricSpace {
+        points: Vec<Vec<f64>>,
+    }
+
+    impl ManhattanMetricSpace {
+        pub fn new(points: Vec<Vec<f64>>) -> Self {
+            validate_points(&points);
+
+            Self { points }
+        }
+
+        pub fn get_point(&self, index: usize) -> &[f64] {
+            return &self.points[index];
+        }
+    }
+
+    impl MetricSpace for ManhattanMetricSpace {
+        fn get_size(&self) -> usize {
+            return self.points.len();
+        }
+
+        fn distance(&self, i: usize, j: usize) -> f64 {
+            return self.points[i]
+                .iter()
+                .zip(self.points[j].iter())
+                .map(|(a, b)| (a - b).abs())
+                .sum::<f64>();
+        }
+    }
+
+    // Points of R^n with the L-infinity (maximum) distance
+    #[derive(Debug, Clone)]
+    pub struct ChebyshevMetricSpace {
+        points: Vec<Vec<f64>>,
+    }
+
+    impl ChebyshevMetricSpace {
+        pub fn new(points: Vec<Vec<f64>>) -> Self {
+            validate_points(&points);
+
+            Self { points }
+        }
+
+        pub fn get_point(&self, index: usize) -> &[f64] {
+            return &self.points[index];
+        }
+    }
+
+    impl MetricSpace for ChebyshevMetricSpace {
+        fn get_size(&self) -> usize {
+            return self.points.len();
+        }
+
+        fn distance(&self, i: usize, j: usize) -> f64 {
+            return self.points[i]
+                .iter()
+                .zip(self.points[j].iter())
+                .map(|(a, b)| (a - b).abs())
+                .fold(0.0, f64::max);
+        }
+    }
+
+    // A metric given by an explicit symmetric matrix of pairwise distances (or more
+    // generally dissimilarities), analogue of JavaPlex's ExplicitMetricSpace
+    #[derive(Debug, Clone)]
+    pub struct ExplicitMetricSpace {
+        distances: Vec<Vec<f64>>,
+    }
+
+    impl ExplicitMetricSpace {
+        pub fn new(distances: Vec<Vec<f64>>) -> Self {
+            let size: usize = distances.len();
+
+            // Every row is checked before reading the transposed entries
+            assert!(
+                distances.iter().all(|row| row.len() == size),
+                "The distance matrix must be square"
+            );
+
+            for (i, row) in distances.iter().enumerate() {
+                assert_eq!(row[i], 0.0, "The distance matrix must have a zero diagonal");
+
+                for (j, d) in row.iter().enumerate() {
+                    assert!(
+                        *d >= 0.0 && d.partial_cmp(&distances[j][i]) == Some(Ordering::Equal),
+                        "The distance matrix must be symmetric and non-negative"
+                    );
+                }
+            }
+
+            Self { distances }
+        }
+    }
+
+    impl MetricSpace for ExplicitMetricSpace {
+        fn get_size(&self) -> usize {
+            return self.distances.len();
+        }
+
+        fn distance(&self, i: usize, j: usize) -> f64 {
+            return self.distances[i][j];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::metric_spaces::{
+        ChebyshevMetricSpace, EuclideanMetricSpace, ExplicitMetricSpace, ManhattanMetricSpace,
+        MetricSpace,
+    };
+
+    fn points() -> Vec<Vec<f64>> {
+        return vec![
+            vec![0.0, 0.0],
+            vec![3.0, 4.0],
+            vec![1.0, 0.0],
+            vec![0.0, -2.0],
+        ];
+    }
+
+    #[test]
+    fn test_point_cloud_distances() {
+        let euclidean: EuclideanMetricSpace = EuclideanMetricSpace::new(points());
+        let manhattan: ManhattanMetricSpace = ManhattanMetricSpace::new(points());
+        let chebyshev: ChebyshevMetricSpace = ChebyshevMetricSpace::new(points());
+
+        assert_eq!(euclidean.get_size(), 4);
+        assert_eq!(euclidean.distance(0, 1), 5.0);
+        assert_eq!(manhattan.distance(0, 1), 7.0);
+        assert_eq!(chebyshev.distance(0, 1), 4.0);
+        assert_eq!(chebyshev.get_point(3), &[0.0, -2.0]);
+    }
+
+    #[test]
+    fn test_neighbor_searches() {
+        let euclidean: EuclideanMetricSpace = EuclideanMetricSpace::new(points());
+
+        assert_eq!(euclidean.get_k_nearest_neighbors(0, 2), vec![2, 3]);
+        assert_eq!(euclidean.get_k_nearest_neighbors(0, 10), vec![2, 3, 1]);
+        assert_eq!(euclidean.get_neighbors_within_radius(0, 2.0), vec![2, 3]);
+        assert!(euclidean.get_neighbors_within_radius(1, 1.0).is_empty());
+    }
+
+    #[test]
+    fn test_explicit_metric_space() {
+        let explicit: ExplicitMetricSpace = ExplicitMetricSpace::new(vec![
+            vec![0.0, 2.0, 1.0],
+            vec![2.0, 0.0, 1.5],
+            vec![1.0, 1.5, 0.0],
+        ]);
+
+        assert_eq!(explicit.get_size(), 3);
+        assert_eq!(explicit.distance(2, 1), 1.5);
+        assert_eq!(explicit.get_k_nearest_neighbors(1, 1), vec![2]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_explicit_metric_space_rejects_asymmetric_matrices() {
+        ExplicitMetricSpace::new(vec![vec![0.0, 1.0], vec![2.0, 0.0]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "The distance matrix must be square")]
+    fn test_explicit_metric_space_rejects_ragged_matrices() {
+        ExplicitMetricSpace::new(vec![vec![0.0, 1.0], vec![]]);
+    }
+}
@@ -0,0 +1,228 @@
+#![allow(dead_code)]
+pub mod barcode_visualizer {
+    use std::fmt::Debug;
+
+    use crate::barcode_collections::barcode_collections::{
+        AnnotatedBarcodeCollection, BarcodeCollection,
+    };
+    use crate::intervals::intervals::Interval;
+    use crate::svg_document::svg_document::{
+        compute_range, compute_ticks, format_number, SvgDocument,
+    };
+
+    const MARGIN_LEFT: f64 = 40.0;
+    const MARGIN_RIGHT: f64 = 30.0;
+    const TITLE_HEIGHT: f64 = 40.0;
+    const LABEL_HEIGHT: f64 = 24.0;
+    const BAR_SPACING: f64 = 10.0;
+    const AXIS_HEIGHT: f64 = 36.0;
+    const ARROW_SIZE: f64 = 5.0;
+    const NUM_TICKS: usize = 5;
+
+    // Draws barcodes as SVG, one panel per dimension with a horizontal bar for every
+    // interval, in the order the collection holds them. Infinite ends are drawn up to the
+    // edge of the plot and finished with an arrow. Analogue of JavaPlex's BarcodeVisualizer.
+    // Without an explicit axis range, the range of the finite endpoints is used.
+    pub struct BarcodeVisualizer {
+        title: String,
+        axis_range: Option<(f64, f64)>,
+        width: f64,
+    }
+
+    impl BarcodeVisualizer {
+        pub fn new(title: &str, axis_range: Option<(f64, f64)>, width: f64) -> Self {
+            if let Some((min, max)) = axis_range {
+                assert!(min < max, "The axis range must be non-empty");
+            }
+            assert!(
+                width > MARGIN_LEFT + MARGIN_RIGHT,
+                "The plot is too narrow to draw"
+            );
+
+            Self {
+                title: String::from(title),
+                axis_range,
+                width,
+            }
+        }
+
+        pub fn draw_barcodes<T, G>(&self, barcodes: &BarcodeCollection<T, G>) -> String
+        where
+            T: Clone + Debug + PartialEq + PartialOrd + Into<f64>,
+            G: Clone + Debug + Default,
+        {
+            return self.draw_annotated_barcodes(barcodes.get_annotated_collection());
+        }
+
+        pub fn draw_annotated_barcodes<T, G>(
+            &self,
+            barcodes: &AnnotatedBarcodeCollection<T, G>,
+        ) -> String
+        where
+            T: Clone + Debug + PartialEq + PartialOrd + Into<f64>,
+            G: Clone + Debug + Default,
+        {
+            let panels: Vec<(u32, Vec<Interval<T>>)> = barcodes
+                .get_dimensions()
+                .into_iter()
+                .map(|dimension| (dimension, barcodes.get_intervals_at_dimension(dimension)))
+                .collect();
+
+            return self.draw_panels(&panels);
+        }
+
+        // Panels are drawn top to bottom in the given order
+        pub fn draw_panels<T>(&self, panels: &[(u32, Vec<Interval<T>>)]) -> String
+        where
+            T: Clone + Debug + PartialEq + PartialOrd + Into<f64>,
+        {
+            let range: (f64, f64) = self.axis_range.unwrap_or_else(|| {
+                compute_range(panels.iter().flat_map(|(_, intervals)| {
+                    intervals.iter().flat_map(|i| {
+                        let start: Option<f64> = i.clone().get_start().map(Into::into);
+                        let end: Option<f64> = i.clone().get_end().map(Into::into);
+
+                        start.into_iter().chain(end)
+                    })
+                }))
+            });
+
+            let height: f64 = TITLE_HEIGHT
+                + panels
+                    .iter()
+                    .map(|(_, intervals)| Self::panel_height(intervals.len()))
+                    .sum::<f64>();
+            let mut document: SvgDocument = SvgDocument::new(self.width, height);
+            document.add_text((self.width / 2.0, 26.0), &self.title, 16.0, "middle");
+
+            let mut top: f64 = TITLE_HEIGHT;
+
+            for (dimension, intervals) in panels.iter() {
+                self.draw_panel(&mut document, top, *dimension, intervals, range);
+                top += Self::panel_height(intervals.len());
+            }
+
+            return document.render();
+        }
+
+        fn panel_height(num_intervals: usize) -> f64 {
+            return LABEL_HEIGHT + BAR_SPACING * (num_intervals as f64 + 1.0) + AXIS_HEIGHT;
+        }
+
+        fn to_x(&self, value: f64, range: (f64, f64)) -> f64 {
+            let plot_width: f64 = self.width - MARGIN_LEFT - MARGIN_RIGHT;
+            let clamped: f64 = value.clamp(range.0, range.1);
+
+            return MARGIN_LEFT + (clamped - range.0) / (range.1 - range.0) * plot_width;
+        }
+
+        fn draw_panel<T>(
+            &self,
+            document: &mut SvgDocument,
+            top: f64,
+            dimension: u32,
+            intervals: &[Interval<T>],
+            range: (f64, f64),
+        ) where
+            T: Clone + Debug + PartialEq + PartialOrd + Into<f64>,
+        {
+            let (left, right): (f64, f64) = (self.to_x(range.0, range), self.to_x(range.1, range));
+
+            document.add_text(
+                (MARGIN_LEFT, top + 16.0),
+                &format!("Dimension: {}", dimension),
+                13.0,
+                "start",
+            );
+
+            for (index, interval) in intervals.iter().enumerate() {
+                let y: f64 = top + LABEL_HEIGHT + BAR_SPACING * (index as f64 + 1.0);
+                let start: f64 = match interval.clone().get_start() {
+                    Some(start) => self.to_x(start.into(), range),
+                    None => left,
+                };
+                let end: f64 = match interval.clone().get_end() {
+                    Some(end) => self.to_x(end.into(), range),
+                    None => right,
+                };
+
+                document.add_line((start, y), (end, y), "black", 2.0);
+
+                if interval.is_right_infinite() {
+                    document.add_polygon(
+                        &[
+                            (right + ARROW_SIZE, y),
+                            (right - ARROW_SIZE, y - ARROW_SIZE),
+                            (right - ARROW_SIZE, y + ARROW_SIZE),
+                        ],
+                        "black",
+                    );
+                }
+
+                if interval.is_left_infinite() {
+                    document.add_polygon(
+                        &[
+                            (left - ARROW_SIZE, y),
+                            (left + ARROW_SIZE, y - ARROW_SIZE),
+                            (left + ARROW_SIZE, y + ARROW_SIZE),
+                        ],
+                        "black",
+                    );
+                }
+            }
+
+            let axis: f64 = top + LABEL_HEIGHT + BAR_SPACING * (intervals.len() as f64 + 1.0);
+            document.add_line((left, axis), (right, axis), "gray", 1.0);
+
+            for tick in compute_ticks(range, NUM_TICKS) {
+                let x: f64 = self.to_x(tick, range);
+                document.add_line((x, axis), (x, axis + 4.0), "gray", 1.0);
+                document.add_text((x, axis + 16.0), &format_number(tick), 10.0, "middle");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::barcode_visualizer::BarcodeVisualizer;
+    use crate::barcode_collections::barcode_collections::BarcodeCollection;
+    use crate::intervals::intervals::Interval;
+
+    fn barcodes() -> BarcodeCollection<f64, ()> {
+        let mut barcodes: BarcodeCollection<f64, ()> = BarcodeCollection::default();
+        barcodes.add_right_infinite_interval(0, 0.0);
+        barcodes.add_interval(0, 0.0, 1.0);
+        barcodes.add_interval(1, 1.0, 2.0);
+        barcodes.add_exact_interval(1, Interval::left_infinite_right_open_interval(4.0));
+
+        return barcodes;
+    }
+
+    #[test]
+    fn test_barcode_panels_and_arrows() {
+        let visualizer: BarcodeVisualizer = BarcodeVisualizer::new("Circle & friends", None, 470.0);
+        let svg: String = visualizer.draw_barcodes(&barcodes());
+
+        assert!(svg.contains(">Circle &amp; friends</text>"));
+        assert!(svg.contains(">Dimension: 0</text>"));
+        assert!(svg.contains(">Dimension: 1</text>"));
+        assert_eq!(svg.matches("stroke-width=\"2\"").count(), 4);
+        assert_eq!(svg.matches("<polygon").count(), 2);
+
+        // The finite range [0, 4] spans the 400 pixels between the margins, and [0, 1) is the
+        // second bar of the first panel
+        assert!(svg.contains("<line x1=\"40\" y1=\"84\" x2=\"140\" y2=\"84\""));
+        assert!(svg.contains(">4</text>"));
+    }
+
+    #[test]
+    fn test_explicit_axis_range_clamps_bars() {
+        let visualizer: BarcodeVisualizer = BarcodeVisualizer::new("", Some((0.0, 0.5)), 470.0);
+        let svg: String = visualizer.draw_barcodes(&barcodes());
+
+        assert!(svg.contains("<line x1=\"40\" y1=\"84\" x2=\"440\" y2=\"84\""));
+        assert!(svg.contains(">0.5</text>"));
+        assert!(!svg.contains(">4</text>"));
+    }
+}
@@ -0,0 +1,308 @@
+#![allow(dead_code)]
+pub mod persistence_diagram_visualizer {
+    use std::fmt::Debug;
+
+    use crate::barcode_collections::barcode_collections::BarcodeCollection;
+    use crate::intervals::intervals::Interval;
+    use crate::persistence_invariant_descriptor::persistence_invariant_descriptor::PersistenceInvariantDescriptor;
+    use crate::svg_document::svg_document::{
+        compute_range, compute_ticks, format_number, SvgDocument,
+    };
+
+    // The margins leave a square plot, so that the diagonal is drawn at 45 degrees
+    const MARGIN_LEFT: f64 = 70.0;
+    const MARGIN_RIGHT: f64 = 40.0;
+    const MARGIN_TOP: f64 = 70.0;
+    const MARGIN_BOTTOM: f64 = 40.0;
+    // Distance of the lines holding the infinite ends from the finite part of the plot
+    const INFINITY_OFFSET: f64 = 18.0;
+    const POINT_RADIUS: f64 = 3.5;
+    const NUM_TICKS: usize = 5;
+    const COLORS: [&str; 6] = [
+        "#1f77b4", "#d62728", "#2ca02c", "#ff7f0e", "#9467bd", "#8c564b",
+    ];
+
+    // A point of the diagram, None standing for an infinite end, with its multiplicity
+    type DiagramPoint = (Option<f64>, Option<f64>, usize);
+
+    // Draws persistence diagrams as SVG: every interval becomes the point (birth, death),
+    // coloured by dimension, above the dashed diagonal. Right-infinite intervals sit on a
+    // dashed line labelled inf above the plot, and left-infinite ones on a line labelled -inf
+    // to its left. Coinciding points are drawn once, annotated with their multiplicity.
+    // Without an explicit axis range, the range of the finite endpoints is used for both axes.
+    // Intervals with a NaN endpoint have no place in the diagram and are skipped.
+    pub struct PersistenceDiagramVisualizer {
+        title: String,
+        axis_range: Option<(f64, f64)>,
+        size: f64,
+    }
+
+    impl PersistenceDiagramVisualizer {
+        pub fn new(title: &str, axis_range: Option<(f64, f64)>, size: f64) -> Self {
+            if let Some((min, max)) = axis_range {
+                assert!(min < max, "The axis range must be non-empty");
+            }
+            assert!(
+                size > MARGIN_LEFT + MARGIN_RIGHT,
+                "The plot is too small to draw"
+            );
+
+            Self {
+                title: String::from(title),
+                axis_range,
+                size,
+            }
+        }
+
+        pub fn draw_barcodes<T, G>(&self, barcodes: &BarcodeCollection<T, G>) -> String
+        where
+            T: Clone + Debug + PartialEq + PartialOrd + Into<f64>,
+            G: Clone + Debug + Default,
+        {
+            return self.draw_diagram(
+                barcodes
+                    .get_annotated_collection()
+                    .get_persistence_invariant_descriptor(),
+            );
+        }
+
+        pub fn draw_diagram<T, G>(
+            &self,
+            descriptor: &PersistenceInvariantDescriptor<Interval<T>, G>,
+        ) -> String
+        where
+            T: Clone + Debug + PartialEq + PartialOrd + Into<f64>,
+        {
+            let mut dimensions: Vec<u32> = descriptor.intervals.keys().cloned().collect();
+            dimensions.sort();
+
+            let diagram: Vec<(u32, Vec<DiagramPoint>)> = dimensions
+                .iter()
+                .map(|dimension| {
+                    (
+                        *dimension,
+                        Self::collect_points(&descriptor.intervals[dimension]),
+                    )
+                })
+                .collect();
+            let range: (f64, f64) = self.axis_range.unwrap_or_else(|| {
+                compute_range(diagram.iter().flat_map(|(_, points)| {
+                    points
+                        .iter()
+                        .flat_map(|(birth, death, _)| birth.iter().chain(death.iter()).cloned())
+                }))
+            });
+            let has_right_infinite: bool = diagram
+                .iter()
+                .any(|(_, points)| points.iter().any(|(_, death, _)| death.is_none()));
+            let has_left_infinite: bool = diagram
+                .iter()
+                .any(|(_, points)| points.iter().any(|(birth, _, _)| birth.is_none()));
+
+            let mut document: SvgDocument = SvgDocument::new(self.size, self.size);
+            document.add_text((self.size / 2.0, 26.0), &self.title, 16.0, "middle");
+            self.draw_axes(&mut document, range, has_left_infinite, has_right_infinite);
+
+            for (index, (dimension, points)) in diagram.iter().enumerate() {
+                let color: &str = COLORS[*dimension as usize % COLORS.len()];
+
+                for (birth, death, multiplicity) in points.iter() {
+                    let x: f64 = match birth {
+                        Some(birth) => self.to_x(*birth, range),
+                        None => MARGIN_LEFT - INFINITY_OFFSET,
+                    };
+                    let y: f64 = match death {
+                        Some(death) => self.to_y(*death, range),
+                        None => MARGIN_TOP - INFINITY_OFFSET,
+                    };
+
+                    document.add_circle((x, y), POINT_RADIUS, color, "none");
+
+                    if *multiplicity > 1 {
+                        document.add_text(
+                            (x + POINT_RADIUS + 2.0, y - POINT_RADIUS),
+                            &multiplicity.to_string(),
+                            9.0,
+                            "start",
+                        );
+                    }
+                }
+
+                // Legend in the lower right corner, below the diagonal where no points lie
+                let legend_y: f64 =
+                    self.size - MARGIN_BOTTOM - 12.0 * (diagram.len() - index) as f64;
+                let legend_x: f64 = self.size - MARGIN_RIGHT - 40.0;
+                document.add_circle((legend_x, legend_y - 3.0), POINT_RADIUS, color, "none");
+                document.add_text(
+                    (legend_x + 8.0, legend_y),
+                    &format!("H{}", dimension),
+                    10.0,
+                    "start",
+                );
+            }
+
+            return document.render();
+        }
+
+        // Distinct points with their multiplicities, sorted by birth and then death
+        fn collect_points<T>(intervals: &[Interval<T>]) -> Vec<DiagramPoint>
+        where
+            T: Clone + Debug + PartialEq + PartialOrd + Into<f64>,
+        {
+            let mut points: Vec<(Option<f64>, Option<f64>)> = intervals
+                .iter()
+                .map(|i| {
+                    (
+                        i.clone().get_start().map(Into::into),
+                        i.clone().get_end().map(Into::into),
+                    )
+                })
+                .filter(|(birth, death)| {
+                    !birth.is_some_and(f64::is_nan) && !death.is_some_and(f64::is_nan)
+                })
+                .collect();
+
+            // None sorts first, which puts -inf births first but inf deaths before finite ones;
+            // only grouping equal points matters here
+            let compare = |a: &Option<f64>, b: &Option<f64>| match (a, b) {
+                (Some(a), Some(b)) => a.total_cmp(b),
+                _ => a.is_some().cmp(&b.is_some()),
+            };
+            points.sort_by(|a, b| compare(&a.0, &b.0).then(compare(&a.1, &b.1)));
+
+            let mut distinct: Vec<DiagramPoint> = Vec::new();
+
+            for (birth, death) in points {
+                match distinct.last_mut() {
+                    Some(last) if last.0 == birth && last.1 == death => last.2 += 1,
+                    _ => distinct.push((birth, death, 1)),
+                }
+            }
+
+            return distinct;
+        }
+
+        fn to_x(&self, value: f64, range: (f64, f64)) -> f64 {
+            let plot_width: f64 = self.size - MARGIN_LEFT - MARGIN_RIGHT;
+            let clamped: f64 = value.clamp(range.0, range.1);
+
+            return MARGIN_LEFT + (clamped - range.0) / (range.1 - range.0) * plot_width;
+        }
+
+        fn to_y(&self, value: f64, range: (f64, f64)) -> f64 {
+            let plot_height: f64 = self.size - MARGIN_TOP - MARGIN_BOTTOM;
+            let clamped: f64 = value.clamp(range.0, range.1);
+
+            return self.size
+                - MARGIN_BOTTOM
+                - (clamped - range.0) / (range.1 - range.0) * plot_height;
+        }
+
+        fn draw_axes(
+            &self,
+            document: &mut SvgDocument,
+            range: (f64, f64),
+            has_left_infinite: bool,
+            has_right_infinite: bool,
+        ) {
+            let (left, right): (f64, f64) = (self.to_x(range.0, range), self.to_x(range.1, range));
+            let (bottom, top): (f64, f64) = (self.to_y(range.0, range), self.to_y(range.1, range));
+
+            document.add_line((left, bottom), (right, bottom), "gray", 1.0);
+            document.add_line((left, bottom), (left, top), "gray", 1.0);
+            document.add_dashed_line((left, bottom), (right, top), "gray", 1.0);
+
+            for tick in compute_ticks(range, NUM_TICKS) {
+                let (x, y): (f64, f64) = (self.to_x(tick, range), self.to_y(tick, range));
+                let label: String = format_number(tick);
+
+                document.add_line((x, bottom), (x, bottom + 4.0), "gray", 1.0);
+                document.add_text((x, bottom + 16.0), &label, 10.0, "middle");
+                document.add_line((left - 4.0, y), (left, y), "gray", 1.0);
+                document.add_text((left - 6.0, y + 3.0), &label, 10.0, "end");
+            }
+
+            if has_right_infinite {
+                let y: f64 = MARGIN_TOP - INFINITY_OFFSET;
+                document.add_dashed_line((left, y), (right, y), "gray", 1.0);
+                document.add_text((left - 6.0, y + 3.0), "inf", 10.0, "end");
+            }
+
+            if has_left_infinite {
+                let x: f64 = MARGIN_LEFT - INFINITY_OFFSET;
+                document.add_dashed_line((x, bottom), (x, top), "gray", 1.0);
+                document.add_text((x, bottom + 16.0), "-inf", 10.0, "middle");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::persistence_diagram_visualizer::PersistenceDiagramVisualizer;
+    use crate::barcode_collections::barcode_collections::BarcodeCollection;
+    use crate::intervals::intervals::Interval;
+    use crate::persistence_invariant_descriptor::persistence_invariant_descriptor::PersistenceInvariantDescriptor;
+
+    #[test]
+    fn test_points_colours_and_multiplicities() {
+        let mut descriptor: PersistenceInvariantDescriptor<Interval<f64>, ()> =
+            PersistenceInvariantDescriptor::new();
+        descriptor.add_interval(0, Interval::right_infinite_right_open_interval(0.0), ());
+        descriptor.add_interval(0, Interval::finite_right_open_interval(0.0, 1.0), ());
+        descriptor.add_interval(0, Interval::finite_right_open_interval(0.0, 1.0), ());
+        descriptor.add_interval(0, Interval::finite_closed_interval(0.0, 1.0), ());
+        descriptor.add_interval(1, Interval::finite_right_open_interval(1.0, 2.0), ());
+
+        let visualizer: PersistenceDiagramVisualizer =
+            PersistenceDiagramVisualizer::new("Diagram", None, 300.0);
+        let svg: String = visualizer.draw_diagram(&descriptor);
+
+        // Two distinct points and the legend in dimension 0, one point and the legend in 1
+        assert_eq!(svg.matches("fill=\"#1f77b4\"").count(), 3);
+        assert_eq!(svg.matches("fill=\"#d62728\"").count(), 2);
+        assert!(svg.contains(">3</text>"));
+        assert!(svg.contains(">H1</text>"));
+
+        // The range [0, 2] spans 190 pixels: (1, 2) is drawn at the top of the plot, and
+        // (0, inf) on the infinity line above it
+        assert!(svg.contains("<circle cx=\"165\" cy=\"70\""));
+        assert!(svg.contains("<circle cx=\"70\" cy=\"52\""));
+        assert!(svg.contains(">inf</text>"));
+        assert!(!svg.contains(">-inf</text>"));
+        assert_eq!(svg.matches("stroke-dasharray").count(), 2);
+    }
+
+    #[test]
+    fn test_left_infinite_intervals() {
+        let mut barcodes: BarcodeCollection<f64, ()> = BarcodeCollection::default();
+        barcodes.add_left_infinite_interval(2, 1.0);
+        barcodes.add_interval(2, 0.5, 1.5);
+
+        let visualizer: PersistenceDiagramVisualizer =
+            PersistenceDiagramVisualizer::new("", Some((0.0, 4.0)), 300.0);
+        let svg: String = visualizer.draw_barcodes(&barcodes);
+
+        assert!(svg.contains(">-inf</text>"));
+        assert!(svg.contains("<circle cx=\"52\" cy=\"212.5\""));
+        assert!(svg.contains(">H2</text>"));
+
+        // Colours follow the dimension, not its position among the dimensions present
+        assert_eq!(svg.matches("fill=\"#2ca02c\"").count(), 3);
+        assert!(!svg.contains("#1f77b4"));
+    }
+
+    #[test]
+    fn test_nan_endpoints_are_skipped() {
+        let mut barcodes: BarcodeCollection<f64, ()> = BarcodeCollection::default();
+        barcodes.add_interval(0, 0.0, 1.0);
+        let visualizer: PersistenceDiagramVisualizer =
+            PersistenceDiagramVisualizer::new("", None, 300.0);
+        let expected: String = visualizer.draw_barcodes(&barcodes);
+
+        barcodes.add_interval(0, f64::NAN, 1.0);
+        barcodes.add_right_infinite_interval(0, f64::NAN);
+
+        assert_eq!(visualizer.draw_barcodes(&barcodes), expected);
+    }
+}
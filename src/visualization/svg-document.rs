@@ -0,0 +1,199 @@
+#![allow(dead_code)]
+pub mod svg_document {
+    // A minimal SVG writer shared by the visualizers, which lets plots be produced without
+    // any plotting dependency. Elements are kept as strings in drawing order.
+    pub struct SvgDocument {
+        width: f64,
+        height: f64,
+        elements: Vec<String>,
+    }
+
+    impl SvgDocument {
+        pub fn new(width: f64, height: f64) -> Self {
+            Self {
+                width,
+                height,
+                elements: Vec::new(),
+            }
+        }
+
+        pub fn get_width(&self) -> f64 {
+            return self.width;
+        }
+
+        pub fn get_height(&self) -> f64 {
+            return self.height;
+        }
+
+        pub fn add_line(&mut self, start: (f64, f64), end: (f64, f64), stroke: &str, width: f64) {
+            self.elements.push(format!(
+                "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"{}\" stroke-width=\"{}\"/>",
+                format_number(start.0),
+                format_number(start.1),
+                format_number(end.0),
+                format_number(end.1),
+                stroke,
+                format_number(width)
+            ));
+        }
+
+        pub fn add_dashed_line(
+            &mut self,
+            start: (f64, f64),
+            end: (f64, f64),
+            stroke: &str,
+            width: f64,
+        ) {
+            self.elements.push(format!(
+                "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"{}\" stroke-width=\"{}\" stroke-dasharray=\"4 3\"/>",
+                format_number(start.0),
+                format_number(start.1),
+                format_number(end.0),
+                format_number(end.1),
+                stroke,
+                format_number(width)
+            ));
+        }
+
+        pub fn add_polygon(&mut self, points: &[(f64, f64)], fill: &str) {
+            let points: Vec<String> = points
+                .iter()
+                .map(|(x, y)| format!("{},{}", format_number(*x), format_number(*y)))
+                .collect();
+
+            self.elements.push(format!(
+                "<polygon points=\"{}\" fill=\"{}\"/>",
+                points.join(" "),
+                fill
+            ));
+        }
+
+        pub fn add_circle(&mut self, center: (f64, f64), radius: f64, fill: &str, stroke: &str) {
+            self.elements.push(format!(
+                "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{}\" stroke=\"{}\"/>",
+                format_number(center.0),
+                format_number(center.1),
+                format_number(radius),
+                fill,
+                stroke
+            ));
+        }
+
+        // The anchor is one of SVG's text-anchor values: start, middle or end
+        pub fn add_text(&mut self, position: (f64, f64), text: &str, size: f64, anchor: &str) {
+            self.elements.push(format!(
+                "<text x=\"{}\" y=\"{}\" font-family=\"sans-serif\" font-size=\"{}\" text-anchor=\"{}\">{}</text>",
+                format_number(position.0),
+                format_number(position.1),
+                format_number(size),
+                anchor,
+                escape_text(text)
+            ));
+        }
+
+        pub fn render(&self) -> String {
+            let mut svg: String = format!(
+                "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\">\n",
+                format_number(self.width),
+                format_number(self.height)
+            );
+            svg.push_str(&format!(
+                "<rect width=\"{}\" height=\"{}\" fill=\"white\"/>\n",
+                format_number(self.width),
+                format_number(self.height)
+            ));
+
+            for element in self.elements.iter() {
+                svg.push_str(element);
+                svg.push('\n');
+            }
+
+            svg.push_str("</svg>\n");
+
+            return svg;
+        }
+    }
+
+    pub fn escape_text(text: &str) -> String {
+        return text
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+            .replace('\'', "&apos;");
+    }
+
+    // Numbers with at most two decimals and without trailing zeros
+    pub fn format_number(value: f64) -> String {
+        let formatted: String = format!("{:.2}", value);
+        let formatted: &str = formatted.trim_end_matches('0').trim_end_matches('.');
+
+        return match formatted {
+            "-0" => String::from("0"),
+            _ => String::from(formatted),
+        };
+    }
+
+    // Evenly spaced tick values over the range, both ends included
+    pub fn compute_ticks(range: (f64, f64), num_ticks: usize) -> Vec<f64> {
+        if num_ticks < 2 {
+            return vec![range.0];
+        }
+
+        let step: f64 = (range.1 - range.0) / (num_ticks - 1) as f64;
+
+        return (0..num_ticks).map(|i| range.0 + step * i as f64).collect();
+    }
+
+    // Range of the finite values, widened when degenerate so that it can be drawn
+    pub fn compute_range<I>(values: I) -> (f64, f64)
+    where
+        I: IntoIterator<Item = f64>,
+    {
+        let (min, max): (f64, f64) = values
+            .into_iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| {
+                (min.min(v), max.max(v))
+            });
+
+        if min > max {
+            return (0.0, 1.0);
+        }
+
+        if min == max {
+            return (min, min + 1.0);
+        }
+
+        return (min, max);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::svg_document::{compute_range, compute_ticks, format_number, SvgDocument};
+
+    #[test]
+    fn test_rendering_escapes_text() {
+        let mut document: SvgDocument = SvgDocument::new(100.0, 50.0);
+        document.add_line((0.0, 0.5), (10.0, 0.5), "black", 1.0);
+        document.add_text((5.0, 5.0), "H<1> & \"H2\"", 12.0, "middle");
+
+        let svg: String = document.render();
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"100\""));
+        assert!(svg.contains("<line x1=\"0\" y1=\"0.5\" x2=\"10\" y2=\"0.5\""));
+        assert!(svg.contains("H&lt;1&gt; &amp; &quot;H2&quot;"));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn test_numbers_ticks_and_ranges() {
+        assert_eq!(format_number(1.0), "1");
+        assert_eq!(format_number(0.125), "0.12");
+        assert_eq!(format_number(-0.001), "0");
+        assert_eq!(compute_ticks((0.0, 2.0), 5), vec![0.0, 0.5, 1.0, 1.5, 2.0]);
+        assert_eq!(compute_range(vec![3.0, 1.0, 2.0]), (1.0, 3.0));
+        assert_eq!(compute_range(vec![2.0]), (2.0, 3.0));
+        assert_eq!(compute_range(Vec::new()), (0.0, 1.0));
+    }
+}